@@ -7,6 +7,23 @@ CONFIG_DIR = "C:/Users/c.casadiego/Documents/Algoritmo_approx_rcspp/rcspp_approx
 CARGO_PROJECT_DIR = "C:/Users/c.casadiego/Documents/Algoritmo_approx_rcspp/rcspp_approx"
 RESULTS_DIR = "C:/Users/c.casadiego/Documents/Algoritmo_approx_rcspp/rcspp_approx/resultados"
 DEBUG_DIR = "C:/Users/c.casadiego/Documents/Algoritmo_approx_rcspp/rcspp_approx/debug_output"
+# Semilla fija para todas las instancias; con None cada corrida elige una y la reporta
+SEED = None
+# Opciones extra para cada corrida, separadas por espacios en la variable de entorno
+# RCSPP_OPCIONES (por ejemplo, "--genetico 50 --codificacion camino --hormigas 20" para
+# comparar las metaheurísticas); por defecto no se agrega ninguna
+OPCIONES = os.environ.get("RCSPP_OPCIONES", "").split()
+
+# columna de la tabla -> etiqueta con que el pulso imprime cada estadística
+ESTADISTICAS = {
+    "Expandidos": "Pulsos expandidos",
+    "PodasDominancia": "Podas por dominancia",
+    "PodasCota": "Podas por cota",
+    "PodasLagrangiana": "Podas por cota lagrangiana",
+    "PodasInfactibilidad": "Podas por infactibilidad",
+    "ProfundidadMax": "Profundidad máxima",
+    "MejorasIncumbente": "Mejoras del incumbente",
+}
 
 os.makedirs(RESULTS_DIR, exist_ok=True)
 os.makedirs(DEBUG_DIR, exist_ok=True)
@@ -22,7 +39,9 @@ def parse_config(file_path):
     return data["DataFile"], data["StartNode"], data["EndNode"], data["TimeConstraint"]
 
 def ejecutar_instancia(graph_file, start, end, constraint):
-    cmd = ["cargo", "run", graph_file, start, end, constraint]
+    cmd = ["cargo", "run", graph_file, start, end, constraint] + OPCIONES
+    if SEED is not None:
+        cmd += ["--semilla", str(SEED)]
     result = subprocess.run(cmd, capture_output=True, text=True, cwd=CARGO_PROJECT_DIR)
     return result.stdout + result.stderr  # Captura también errores
 
@@ -38,6 +57,11 @@ def parse_output(output):
         consumo = re.search(r"Consumo total:\s*(\d+)", bloque)
         duracion = re.search(r"Duration:\s*([\d\.]+s|[\d\.]+ms)", bloque)
         approx = re.search(r"Approximation:\s*([\d\.]+)", bloque)
+        # estadísticas de búsqueda (solo las imprimen los algoritmos del pulso)
+        estadisticas = {
+            columna: int(m.group(1)) if (m := re.search(etiqueta + r":\s*(\d+)", bloque)) else None
+            for columna, etiqueta in ESTADISTICAS.items()
+        }
 
         data.append({
             "Algoritmo": nombre,
@@ -45,7 +69,8 @@ def parse_output(output):
             "Costo": int(costo.group(1)) if costo else None,
             "Consumo": int(consumo.group(1)) if consumo else None,
             "Duración": duracion.group(1) if duracion else "",
-            "Approx": float(approx.group(1)) if approx else None
+            "Approx": float(approx.group(1)) if approx else None,
+            **estadisticas
         })
     return data
 
@@ -84,8 +109,10 @@ def main():
                 print(f"⚠️ No se extrajo información útil de {filename}")
                 continue
 
+            semilla = re.search(r"Semilla:\s*(\d+)", output)
             for alg in algoritmo_data:
                 alg["Config"] = filename
+                alg["Semilla"] = semilla.group(1) if semilla else ""
 
             # Guardar archivo intermedio
             df_partial = pd.DataFrame(algoritmo_data)
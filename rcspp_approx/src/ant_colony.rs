@@ -0,0 +1,252 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::pulse_algorithm::{get_bounds, Budget};
+use crate::weight::Weight;
+
+// peso de la feromona y de la visibilidad en la probabilidad de elegir un arco
+const ALPHA: f64 = 1.0;
+const BETA: f64 = 2.0;
+
+// feromona inicial de cada arco y piso tras evaporar, para que ningún arco quede descartado
+const INITIAL_PHEROMONE: f64 = 1.0;
+const MIN_PHEROMONE: f64 = 0.01;
+
+/// Parámetros de la colonia de hormigas.
+#[derive(Debug, Clone, Copy)]
+pub struct AntColonyParams {
+    /// hormigas por iteración
+    pub colony_size: usize,
+    pub iterations: usize,
+    /// fracción de la feromona que se evapora en cada iteración, en (0, 1]
+    pub evaporation: f64,
+}
+
+/// Mejor camino factible que encontró la colonia.
+#[derive(Debug, Clone)]
+pub struct AntColonyResult<W> {
+    pub best: Option<(Vec<usize>, W, W)>,
+    /// iteraciones completas
+    pub iterations: usize,
+    /// si se acabó el presupuesto antes de hacer todas las iteraciones
+    pub interrupted: bool,
+}
+
+/// Optimización por colonia de hormigas (ACO).
+///
+/// Cada hormiga arma un camino de `s` a `e` eligiendo el próximo arco al azar con
+/// probabilidad proporcional a `feromona^α · visibilidad^β`. La visibilidad sale de las
+/// mismas cotas de `get_bounds` que usa el pulso: un arco es más visible cuanto menos se
+/// aparta del camino de mínimo costo hacia `e` (costo reducido `c(u, v) + min_costo(v) -
+/// min_costo(u)`), y solo se ofrecen los arcos tras los cuales todavía cabe el consumo
+/// mínimo hasta `e`. Al final de cada iteración se evapora la feromona y la refuerzan la
+/// mejor hormiga de la iteración y la mejor hasta el momento.
+pub fn ant_colony<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, params: &AntColonyParams, seed: u64, budget: &Budget) -> AntColonyResult<W> {
+    let AntColonyParams {colony_size, iterations, evaporation} = *params;
+    let minimum_consumption = get_bounds(graph, e, |(_a, _b, c)| c);
+    let minimum_cost = get_bounds(graph, e, |(_a, b, _c)| b);
+    if minimum_consumption[s] > resource_limit {
+        return AntColonyResult {best: None, iterations: 0, interrupted: false};
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut colony = Colony::new(graph, e, resource_limit, minimum_consumption, &minimum_cost);
+
+    // cada hormiga marca los nodos que visita con su número, así el vector no se limpia
+    let mut visited = vec![0usize; graph.len()];
+    let mut ant = 0;
+    let mut best: Option<(Vec<usize>, W, W)> = None;
+    // arcos (índices en `pheromone`) del mejor camino
+    let mut best_edges: Vec<usize> = Vec::new();
+
+    for iteration in 0..iterations {
+        let mut iteration_best: Option<Walk<W>> = None;
+        for _ in 0..colony_size {
+            if budget.exhausted() {
+                return AntColonyResult {best, iterations: iteration, interrupted: true};
+            }
+            ant += 1;
+            let Some(walk) = colony.walk(s, &mut visited, ant, &mut rng) else { continue };
+            if iteration_best.as_ref().is_none_or(|current| walk.cost < current.cost) {
+                iteration_best = Some(walk);
+            }
+        }
+
+        colony.evaporate(evaporation);
+        if let Some(walk) = iteration_best {
+            colony.deposit(&walk.edges, walk.cost, minimum_cost[s]);
+            if best.as_ref().is_none_or(|best| walk.cost < best.1) {
+                best = Some((walk.path, walk.cost, walk.consumption));
+                best_edges = walk.edges;
+            }
+        }
+        if let Some(best) = &best {
+            colony.deposit(&best_edges, best.1, minimum_cost[s]);
+        }
+    }
+
+    AntColonyResult {best, iterations, interrupted: false}
+}
+
+// camino de una hormiga
+struct Walk<W> {
+    path: Vec<usize>,
+    cost: W,
+    consumption: W,
+    // índices de los arcos en `pheromone`
+    edges: Vec<usize>,
+}
+
+// grafo, cotas y feromona compartidos por todas las hormigas
+struct Colony<'a, W> {
+    graph: &'a [Vec<(usize, W, W)>],
+    e: usize,
+    resource_limit: W,
+    minimum_consumption: Vec<W>,
+    // la feromona y la visibilidad de los arcos de `u` empiezan en `offsets[u]`
+    offsets: Vec<usize>,
+    pheromone: Vec<f64>,
+    visibility: Vec<f64>,
+}
+
+impl<'a, W: Weight> Colony<'a, W> {
+    fn new(graph: &'a [Vec<(usize, W, W)>], e: usize, resource_limit: W, minimum_consumption: Vec<W>, minimum_cost: &[W]) -> Self {
+        let mut offsets = Vec::with_capacity(graph.len() + 1);
+        offsets.push(0);
+        for adj in graph {
+            offsets.push(offsets.last().unwrap() + adj.len());
+        }
+        let pheromone = vec![INITIAL_PHEROMONE; *offsets.last().unwrap()];
+
+        // los costos reducidos se miden en arcos de costo promedio, así β no depende de la escala
+        let edges = graph.iter().flatten().count().max(1) as f64;
+        let scale = graph.iter().flatten().map(|&(_v, cost, _cons)| cost.to_f64()).sum::<f64>() / edges;
+        let scale = if scale > 0.0 {scale} else {1.0};
+        // factor en (0, 1] que vale 1 sobre los caminos de mínimo costo hacia `e`
+        let visibility = graph.iter().enumerate()
+            .flat_map(|(u, adj)| adj.iter().map(move |&(v, cost, _cons)| (u, v, cost)))
+            .map(|(u, v, cost)| {
+                if minimum_cost[v] == W::MAX {
+                    return 0.0;
+                }
+                let reduced = (cost.to_f64() + minimum_cost[v].to_f64() - minimum_cost[u].to_f64()).max(0.0);
+                (1.0 / (1.0 + reduced / scale)).powf(BETA)
+            })
+            .collect();
+
+        Colony {graph, e, resource_limit, minimum_consumption, offsets, pheromone, visibility}
+    }
+
+    // Camino de una hormiga desde `s`, o `None` si se encierra entre nodos ya visitados
+    fn walk(&self, s: usize, visited: &mut [usize], ant: usize, rng: &mut impl Rng) -> Option<Walk<W>> {
+        let mut path = vec![s];
+        let mut edges = Vec::new();
+        let (mut cost, mut consumption) = (W::ZERO, W::ZERO);
+        let mut node = s;
+        visited[s] = ant;
+        // (índice del arco, peso) de los arcos que se pueden tomar desde `node`
+        let mut candidates: Vec<(usize, f64)> = Vec::new();
+
+        while node != self.e {
+            candidates.clear();
+            for (i, &(v, _cost, edge_consumption)) in self.graph[node].iter().enumerate() {
+                let index = self.offsets[node] + i;
+                // factible: después del arco todavía cabe el consumo mínimo hasta `e`
+                let fits = self.minimum_consumption[v] != W::MAX
+                    && consumption.checked_add(edge_consumption)
+                        .and_then(|used| used.checked_add(self.minimum_consumption[v]))
+                        .is_some_and(|total| total <= self.resource_limit);
+                if visited[v] != ant && fits && self.visibility[index] > 0.0 {
+                    candidates.push((index, self.pheromone[index].powf(ALPHA) * self.visibility[index]));
+                }
+            }
+            let total: f64 = candidates.iter().map(|&(_, weight)| weight).sum();
+            if candidates.is_empty() || total <= 0.0 {
+                return None;
+            }
+
+            // ruleta
+            let mut target = rng.random::<f64>() * total;
+            let mut chosen = candidates[candidates.len() - 1].0;
+            for &(index, weight) in &candidates {
+                if target < weight {
+                    chosen = index;
+                    break;
+                }
+                target -= weight;
+            }
+
+            let (v, edge_cost, edge_consumption) = self.graph[node][chosen - self.offsets[node]];
+            // el consumo cabe por `fits`; si el costo desborda, la hormiga se pierde
+            cost = cost.checked_add(edge_cost)?;
+            consumption = consumption.add(edge_consumption);
+            edges.push(chosen);
+            path.push(v);
+            visited[v] = ant;
+            node = v;
+        }
+
+        Some(Walk {path, cost, consumption, edges})
+    }
+
+    fn evaporate(&mut self, evaporation: f64) {
+        for tau in &mut self.pheromone {
+            *tau = (*tau * (1.0 - evaporation)).max(MIN_PHEROMONE);
+        }
+    }
+
+    // refuerza los arcos de un camino en proporción a qué tan cerca está su costo de la cota
+    fn deposit(&mut self, edges: &[usize], cost: W, lower_bound: W) {
+        let amount = (lower_bound.to_f64() + 1.0) / (cost.to_f64() + 1.0);
+        for &index in edges {
+            self.pheromone[index] += amount;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{is_feasible, limits, optimum, random_graph};
+
+    const PARAMS: AntColonyParams = AntColonyParams {colony_size: 10, iterations: 10, evaporation: 0.1};
+
+    #[test]
+    fn returns_only_feasible_paths() {
+        for seed in 0..20 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                let result = ant_colony(&graph, 0, 8, limit, &PARAMS, seed, &Budget::unlimited());
+                assert!(!result.interrupted);
+                let opt = optimum(&graph, 0, 8, limit);
+                if let Some((path, cost, consumption)) = &result.best {
+                    assert!(is_feasible(&graph, 0, 8, limit, path, *cost, *consumption), "semilla {} límite {}", seed, limit);
+                    assert!(opt <= Some(*cost));
+                }
+                if opt.is_none() {
+                    assert!(result.best.is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_path() {
+        for seed in 0..10 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                let run = || ant_colony(&graph, 0, 8, limit, &PARAMS, seed, &Budget::unlimited()).best;
+                assert_eq!(run(), run());
+            }
+        }
+    }
+
+    #[test]
+    fn exhausted_budget_stops_before_the_first_ant() {
+        let graph = random_graph(0, 9, 0.4);
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = ant_colony(&graph, 0, 8, u32::MAX / 2, &PARAMS, 0, &budget);
+        assert!(result.interrupted);
+        assert_eq!((result.best, result.iterations), (None, 0));
+    }
+}
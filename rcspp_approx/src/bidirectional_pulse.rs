@@ -0,0 +1,178 @@
+use crate::pulse_algorithm::{expand_pulse, initial_incumbent, reverse_graph, Pulse, PulseContext, PulseOptions, PulseResult, PulseStats, PulseStatus, Step};
+use crate::weight::Weight;
+
+/// Camino parcial hacia adelante detenido en la mitad del recurso: (costo, consumo, camino)
+type HalfPath<W> = (W, W, Vec<usize>);
+
+/// Algoritmo del pulso bidireccional.
+///
+/// Los pulsos hacia adelante salen de `s` sobre el grafo original y se detienen
+/// cuando su consumo llega a la mitad de `resource_limit`; esos caminos parciales
+/// quedan guardados en el nodo donde se detuvieron. Luego los pulsos hacia atrás
+/// salen de `e` sobre el grafo invertido, con consumo a lo sumo la otra mitad, y
+/// en cada nodo que visitan intentan unirse con los caminos parciales guardados.
+/// Ambas direcciones comparten la misma cota primal.
+///
+/// No es un encuentro en el medio simultáneo: las dos fases van una después de la otra
+/// y la de atrás necesita la frontera completa de la de adelante, por lo que si el
+/// presupuesto se acaba en la primera fase no se corre la segunda. La frontera guarda,
+/// por nodo, todos los caminos parciales no dominados con su `Vec<usize>` completo: en
+/// memoria ocupa del orden de (caminos guardados) × (largo de la mitad del camino), y la
+/// cantidad de caminos guardados puede crecer exponencialmente con el tamaño del grafo.
+pub fn bidirectional_pulse<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, options: &PulseOptions<W>) -> PulseResult<Pulse<W>> {
+    let graph_rev = reverse_graph(graph);
+    let half = resource_limit.half();
+
+    // cotas hacia `e` para los pulsos hacia adelante y desde `s` para los de atrás
+    let mut stats = PulseStats::new();
+    let forward = PulseContext::new(graph, e, resource_limit).with_lagrangian_bound(s, e, options);
+    // el grafo invertido tiene los mismos caminos: el λ de adelante sirve para atrás
+    let backward = PulseContext::new(&graph_rev, s, resource_limit).with_multiplier(forward.lambda, s);
+    stats.bounds_time = forward.bounds_time + backward.bounds_time;
+    stats.lagrangian_time = forward.lagrangian_time + backward.lagrangian_time;
+
+    let (mut primal_bound, mut best_path) = initial_incumbent(graph, s, e, resource_limit, options);
+    if best_path.is_some() {
+        stats.record_incumbent(primal_bound);
+    }
+    let mut frontier: Vec<Vec<HalfPath<W>>> = vec![Vec::new(); graph.len()];
+
+    // ── Fase 1: pulsos hacia adelante hasta la mitad del recurso ──
+    let mut curr = Pulse::start(s, graph.len());
+    let mut labels = options.labels.build(graph.len(), options.seed);
+    let status = expand_pulse(&forward, &options.budget, &mut primal_bound, labels.as_mut(), &mut curr, &mut stats, |curr, primal_bound, stats| {
+        if curr.last_node == e {
+            if curr.cost < *primal_bound {
+                *primal_bound = curr.cost;
+                best_path = Some(curr.clone());
+                stats.record_incumbent(curr.cost);
+            }
+            Step::Stop
+        } else if curr.consumption >= half {
+            // llegó a la mitad del recurso: se guarda para la fase hacia atrás
+            store_half_path(&mut frontier[curr.last_node], curr);
+            Step::Stop
+        } else {
+            Step::Expand
+        }
+    });
+    if status == PulseStatus::Interrupted {
+        // sin la frontera completa no tiene sentido la fase hacia atrás
+        return PulseResult {best: best_path, status, stats};
+    }
+
+    // ── Fase 2: pulsos hacia atrás que se unen con la frontera ──
+    let mut curr = Pulse::start(e, graph.len());
+    let mut labels = options.labels.build(graph.len(), options.seed);
+    let status = expand_pulse(&backward, &options.budget, &mut primal_bound, labels.as_mut(), &mut curr, &mut stats, |curr, primal_bound, stats| {
+        if curr.consumption > resource_limit.sub(half) {
+            return Step::Stop;
+        }
+
+        if curr.last_node == s {
+            // camino completo encontrado solo con pulsos hacia atrás
+            if curr.cost < *primal_bound {
+                *primal_bound = curr.cost;
+                let path = curr.path.iter().rev().copied().collect();
+                best_path = Some(Pulse::from_path(path, curr.cost, curr.consumption, graph.len()));
+                stats.record_incumbent(curr.cost);
+            }
+            return Step::Stop;
+        }
+
+        if let Some(joined) = join(&frontier[curr.last_node], curr, resource_limit, *primal_bound) {
+            *primal_bound = joined.cost;
+            stats.record_incumbent(joined.cost);
+            best_path = Some(joined);
+        }
+        Step::Expand
+    });
+
+    PulseResult {best: best_path, status, stats}
+}
+
+// Solo se guardan caminos parciales no dominados en (costo, consumo) dentro de cada nodo
+fn store_half_path<W: Weight>(stored: &mut Vec<HalfPath<W>>, curr: &Pulse<W>) {
+    if stored.iter().any(|&(cost, consumption, _)| cost <= curr.cost && consumption <= curr.consumption) {
+        return;
+    }
+    stored.retain(|&(cost, consumption, _)| cost < curr.cost || consumption < curr.consumption);
+    stored.push((curr.cost, curr.consumption, curr.path.clone()));
+}
+
+// Une el pulso hacia atrás `curr` (de `e` a su último nodo) con el mejor de los
+// caminos parciales hacia adelante guardados en ese mismo nodo, si mejora la cota.
+fn join<W: Weight>(stored: &[HalfPath<W>], curr: &Pulse<W>, resource_limit: W, primal_bound: W) -> Option<Pulse<W>> {
+    let (cost, consumption, path) = stored.iter()
+        .filter(|(cost, consumption, _)| cost.checked_add(curr.cost).is_some_and(|total| total < primal_bound) && consumption.add_fits(curr.consumption, resource_limit))
+        // el camino unido debe ser elemental
+        .filter(|(_, _, path)| !path[..path.len() - 1].iter().any(|&v| curr.visited[v]))
+        .min_by(|(a, _, _), (b, _, _)| a.total_cmp(b))?;
+
+    let mut joined = path.clone();
+    joined.extend(curr.path.iter().rev().skip(1));
+    Some(Pulse::from_path(joined, cost.add(curr.cost), consumption.add(curr.consumption), curr.visited.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labels::LabelStrategy;
+    use crate::pulse_algorithm::Budget;
+    use crate::test_graphs::{assert_optimal, diamonds, from_edges, is_feasible, limits, random_graph, Graph};
+
+    // cadena 0 → 1 → 2 → 3 → 4 de consumo 2 por arco y un atajo caro que consume poco
+    fn chain() -> Graph {
+        from_edges(5, &[(0, 1, 1, 2), (1, 2, 1, 2), (2, 3, 1, 2), (3, 4, 1, 2), (0, 4, 10, 1)])
+    }
+
+    #[test]
+    fn joins_paths_at_the_half_point() {
+        // con límite 8 la mitad es 4: la cadena se parte en el nodo 2 y se une desde atrás
+        let graph = chain();
+        let best = bidirectional_pulse(&graph, 0, 4, 8, &PulseOptions::default()).best.unwrap();
+        assert_eq!((best.path, best.cost, best.consumption), (vec![0, 1, 2, 3, 4], 4, 8));
+        // con límite impar la mitad hacia atrás es la más grande
+        assert_eq!(bidirectional_pulse(&graph, 0, 4, 9, &PulseOptions::default()).best.unwrap().path, vec![0, 1, 2, 3, 4]);
+        assert_eq!(bidirectional_pulse(&graph, 0, 4, 7, &PulseOptions::default()).best.unwrap().path, vec![0, 4]);
+        assert!(bidirectional_pulse(&graph, 0, 4, 0, &PulseOptions::default()).best.is_none());
+    }
+
+    #[test]
+    fn long_paths_do_not_overflow_the_stack() {
+        let n = 100_000;
+        let edges: Vec<_> = (1..n).map(|v| (v - 1, v, 1, 1)).collect();
+        let graph = from_edges(n, &edges);
+        let best = bidirectional_pulse(&graph, 0, n - 1, n as u32, &PulseOptions::default()).best.unwrap();
+        assert_eq!((best.path.len(), best.cost), (n, n as u32 - 1));
+    }
+
+    #[test]
+    fn exhausted_budget_returns_the_incumbent() {
+        // con la mitad del recurso tan holgada los pulsos hacia adelante llegan a `e` antes de cortarse
+        let graph = diamonds(20);
+        let e = graph.len() - 1;
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = bidirectional_pulse(&graph, 0, e, u32::MAX / 2, &PulseOptions {budget, ..PulseOptions::default()});
+        assert_eq!(result.status, PulseStatus::Interrupted);
+        let best = result.best.unwrap();
+        assert!(is_feasible(&graph, 0, e, u32::MAX / 2, &best.path, best.cost, best.consumption));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                for labels in [LabelStrategy::Three, LabelStrategy::Pareto { capacity: 2 }] {
+                    for lagrangian in [false, true] {
+                        let result = bidirectional_pulse(&graph, 0, 7, limit, &PulseOptions {labels, lagrangian, ..PulseOptions::default()});
+                        assert_eq!(result.status, PulseStatus::Optimal);
+                        assert_optimal(&graph, 0, 7, limit, result.best.as_ref().map(|best| (&best.path[..], best.cost, best.consumption)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::weight::{Ordered, Weight};
+
+// un desvío re-rutea a lo sumo este número de arcos seguidos del camino
+const SEGMENT_HOPS: usize = 20;
+
+// nodos que fija a lo sumo cada Dijkstra de desvíos, así explora solo los alrededores
+// del tramo aunque algún destino no se alcance
+const DETOUR_NODES: usize = 5000;
+
+// árbol de Dijkstra: (nodo anterior, índice del arco en la adyacencia, consumo acumulado)
+pub(crate) type Tree<W> = HashMap<usize, (usize, usize, W)>;
+
+/// Cambio de los arcos entre las posiciones `from` y `to` de un camino por `edges`
+/// (índices de los arcos en la adyacencia de cada nodo), con el costo y el consumo
+/// del camino completo que queda.
+pub(crate) struct Detour<W> {
+    pub from: usize,
+    pub to: usize,
+    pub edges: Vec<usize>,
+    pub cost: W,
+    pub consumption: W,
+}
+
+/// Vecindario de un camino dado por los índices de sus arcos: los desvíos de sus tramos
+/// que no pasan por el resto del camino. Lo comparten la búsqueda local y la tabú.
+pub(crate) struct Neighborhood<'a, W> {
+    graph: &'a [Vec<(usize, W, W)>],
+    resource_limit: W,
+    pub path: Vec<usize>,
+    // costo y consumo de los primeros `k` arcos
+    prefix: Vec<(W, W)>,
+    position: HashMap<usize, usize>,
+}
+
+impl<'a, W: Weight> Neighborhood<'a, W> {
+    /// `None` si el costo o el consumo del camino desborda.
+    pub(crate) fn new(graph: &'a [Vec<(usize, W, W)>], s: usize, edges: &[usize], resource_limit: W) -> Option<Self> {
+        let path = nodes(graph, s, edges);
+        let mut prefix = vec![(W::ZERO, W::ZERO)];
+        for (&node, &index) in path.iter().zip(edges) {
+            let (_v, edge_cost, edge_consumption) = graph[node][index];
+            let (prev_cost, prev_consumption) = *prefix.last().unwrap();
+            prefix.push((prev_cost.checked_add(edge_cost)?, prev_consumption.checked_add(edge_consumption)?));
+        }
+        let position = path.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        Some(Neighborhood {graph, resource_limit, path, prefix, position})
+    }
+
+    /// (costo, consumo) del camino
+    pub(crate) fn total(&self) -> (W, W) {
+        *self.prefix.last().unwrap()
+    }
+
+    /// posición de `v` en el camino, si está
+    pub(crate) fn position(&self, v: usize) -> Option<usize> {
+        self.position.get(&v).copied()
+    }
+
+    /// El camino con el tramo `from..to` cambiado por los arcos `edges`, que salen de `path[from]`;
+    /// `None` si su costo o su consumo desborda.
+    pub(crate) fn detour(&self, from: usize, to: usize, edges: Vec<usize>) -> Option<Detour<W>> {
+        let (cost, consumption) = totals(self.graph, self.path[from], &edges)?;
+        let (total_cost, total_consumption) = self.total();
+        let (from_cost, from_consumption) = self.prefix[from];
+        let (to_cost, to_consumption) = self.prefix[to];
+        Some(Detour {
+            from,
+            to,
+            edges,
+            cost: total_cost.sub(to_cost.sub(from_cost)).checked_add(cost)?,
+            consumption: total_consumption.sub(to_consumption.sub(from_consumption)).checked_add(consumption)?,
+        })
+    }
+
+    // consumo que puede gastar un desvío del tramo `from..to` sin pasarse del límite
+    fn slack(&self, from: usize, to: usize) -> Option<W> {
+        let (_cost, total_consumption) = self.total();
+        self.resource_limit.checked_sub(total_consumption.sub(self.prefix[to].1.sub(self.prefix[from].1)))
+    }
+
+    /// Desvíos factibles más baratos desde `path[from]` hasta cada uno de los `SEGMENT_HOPS`
+    /// nodos siguientes, sin los arcos `forbidden` (nodo, índice).
+    ///
+    /// Un solo Dijkstra por costo, acotado por la holgura del tramo más largo y por
+    /// `DETOUR_NODES` nodos fijados, da el desvío hacia todos los destinos; si el de algún
+    /// tramo no cabe en su propia holgura (o pasa por nodos posteriores del camino) se
+    /// busca otra vez solo hacia ese destino con esa holgura, así un desvío un poco más caro
+    /// que sí cabe no se pierde. La segunda búsqueda se hace solo si `promising` acepta el
+    /// costo del camino con el desvío que no cabía, que suele ser una cota del que cabe.
+    pub(crate) fn detours(&self, from: usize, forbidden: impl Fn(usize, usize) -> bool, promising: impl Fn(W) -> bool) -> Vec<Detour<W>> {
+        let u = self.path[from];
+        let last = (from + SEGMENT_HOPS).min(self.path.len() - 1);
+        // sin volver a los nodos anteriores del camino ni, en un tramo `from..to`, pasar por los posteriores a `to`
+        let outside = |to: usize| {
+            let forbidden = &forbidden;
+            move |node: usize, index: usize| {
+                forbidden(node, index) || self.position(self.graph[node][index].0).is_some_and(|pos| pos < from || pos > to)
+            }
+        };
+
+        // si ni el tramo más largo deja holgura, ningún desvío cabe
+        let Some(slack) = self.slack(from, last) else { return Vec::new() };
+        let tree = detour_tree(self.graph, u, |cost, _consumption| cost, outside(last), slack, &self.path[from + 1..=last], DETOUR_NODES);
+        let mut detours = Vec::new();
+        for to in from + 1..=last {
+            let Some(edges) = tree_edges(&tree, u, self.path[to]) else { continue };
+            let passes_later = nodes(self.graph, u, &edges).iter().any(|&v| self.position(v).is_some_and(|pos| pos > to));
+            let Some(detour) = self.detour(from, to, edges) else { continue };
+            if !passes_later && detour.consumption <= self.resource_limit {
+                detours.push(detour);
+                continue;
+            }
+            if !promising(detour.cost) {
+                continue;
+            }
+            let Some(slack) = self.slack(from, to) else { continue };
+            let tree = detour_tree(self.graph, u, |cost, _consumption| cost, outside(to), slack, &[self.path[to]], DETOUR_NODES);
+            if let Some(detour) = tree_edges(&tree, u, self.path[to]).and_then(|edges| self.detour(from, to, edges)) {
+                detours.push(detour);
+            }
+        }
+        detours
+    }
+}
+
+/// Dijkstra desde `from` por `key(costo, consumo)` de cada arco, sin los arcos prohibidos
+/// (nodo, índice) y sin consumir más de `max_consumption`; se detiene al fijar todos los
+/// `targets` o `max_settled` nodos.
+pub(crate) fn detour_tree<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    from: usize,
+    key: fn(W, W) -> W,
+    forbidden: impl Fn(usize, usize) -> bool,
+    max_consumption: W,
+    targets: &[usize],
+    max_settled: usize
+) -> Tree<W> {
+    let mut remaining: HashSet<usize> = targets.iter().copied().collect();
+    let mut dist: HashMap<usize, W> = HashMap::from([(from, W::ZERO)]);
+    let mut tree: Tree<W> = HashMap::new();
+    let mut heap: BinaryHeap<Reverse<(Ordered<W>, usize)>> = BinaryHeap::new();
+    heap.push(Reverse((Ordered(W::ZERO), from)));
+    let mut settled = 0;
+
+    while let Some(Reverse((Ordered(distance), node))) = heap.pop() {
+        if distance > dist[&node] {
+            continue;
+        }
+        remaining.remove(&node);
+        settled += 1;
+        if remaining.is_empty() || settled >= max_settled {
+            break;
+        }
+
+        let consumption = tree.get(&node).map_or(W::ZERO, |entry| entry.2);
+        for (index, &(next, edge_cost, edge_consumption)) in graph[node].iter().enumerate() {
+            if next == from || forbidden(node, index) {
+                continue;
+            }
+            let Some(next_consumption) = consumption.checked_add(edge_consumption).filter(|&c| c <= max_consumption) else { continue };
+            let Some(next_distance) = distance.checked_add(key(edge_cost, edge_consumption)) else { continue };
+            if dist.get(&next).is_none_or(|&old| next_distance < old) {
+                dist.insert(next, next_distance);
+                tree.insert(next, (node, index, next_consumption));
+                heap.push(Reverse((Ordered(next_distance), next)));
+            }
+        }
+    }
+
+    tree
+}
+
+/// Índices de los arcos del árbol de `from` a `v`, si `v` quedó en el árbol.
+pub(crate) fn tree_edges<W>(tree: &Tree<W>, from: usize, v: usize) -> Option<Vec<usize>> {
+    let mut edges = Vec::new();
+    let mut curr = v;
+    while curr != from {
+        let &(prev, index, _) = tree.get(&curr)?;
+        edges.push(index);
+        curr = prev;
+    }
+    edges.reverse();
+    Some(edges)
+}
+
+/// Nodos del camino que sale de `s` por los arcos `edges`.
+pub(crate) fn nodes<W>(graph: &[Vec<(usize, W, W)>], s: usize, edges: &[usize]) -> Vec<usize> {
+    let mut path = vec![s];
+    for &index in edges {
+        path.push(graph[*path.last().unwrap()][index].0);
+    }
+    path
+}
+
+/// (costo, consumo) del camino que sale de `s` por los arcos `edges`, si ninguno desborda.
+pub(crate) fn totals<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, edges: &[usize]) -> Option<(W, W)> {
+    let mut node = s;
+    let (mut cost, mut consumption) = (W::ZERO, W::ZERO);
+    for &index in edges {
+        let (next, edge_cost, edge_consumption) = graph[node][index];
+        cost = cost.checked_add(edge_cost)?;
+        consumption = consumption.checked_add(edge_consumption)?;
+        node = next;
+    }
+    Some((cost, consumption))
+}
@@ -1,24 +1,30 @@
 use std::{cmp::Reverse, collections::BinaryHeap};
 
+use crate::weight::{Ordered, Weight};
 
-fn dijkstra<F>(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize, fn_cost: F,) -> Option<(Vec<usize>, u32, u32)>
+// entrada del heap de Dijkstra: (prioridad, nodo, costo acumulado, consumo acumulado)
+type Entry<W> = Reverse<(Ordered<W>, usize, Ordered<W>, Ordered<W>)>;
+
+
+fn dijkstra<W, F>(graph: &[Vec<(usize,W,W,bool)>], s:usize, e:usize, fn_cost: F,) -> Option<(Vec<usize>, W, W)>
     where
-        F: Fn(u32,u32)->u32, 
+        W: Weight,
+        F: Fn(W,W)->W, 
     {
     // Cola de prioridad para Dijkstra
-    let mut heap: BinaryHeap<Reverse<(u32, usize, u32,u32)>> = BinaryHeap::new();
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
     
     // Inicializamos con el nodo origen (costo, nodo)
-    heap.push(Reverse((0, s,0,0)));
+    heap.push(Reverse((Ordered(W::ZERO), s, Ordered(W::ZERO), Ordered(W::ZERO))));
     
-    let mut dist = vec![u32::MAX; graph.len()];
-    dist[s] = 0;
+    let mut dist = vec![W::MAX; graph.len()];
+    dist[s] = W::ZERO;
     
     // Para reconstruir el camino
     let mut parent = vec![None; graph.len()];
     
 
-    while let Some(Reverse((f_cost, node, accum_cost, accum_resource))) = heap.pop() {
+    while let Some(Reverse((Ordered(f_cost), node, Ordered(accum_cost), Ordered(accum_resource)))) = heap.pop() {
         // Si ya encontramos un mejor camino a este nodo, ignoramos
         if f_cost > dist[node] {
             continue;
@@ -32,8 +38,10 @@ fn dijkstra<F>(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize, fn_cost
 
         // Exploramos los vecinos
         for &(next_node, edge_cost, edge_resource, block) in &graph[node] {
-            let next_cost = accum_cost + edge_cost;
-            let next_resource = accum_resource + edge_resource;
+            // un camino cuya suma desborda no se sigue
+            let (Some(next_cost), Some(next_resource)) = (accum_cost.checked_add(edge_cost), accum_resource.checked_add(edge_resource)) else {
+                continue;
+            };
             
             // Calculamos el costo combinado
             let next_f_cost = fn_cost(next_cost, next_resource);
@@ -44,7 +52,7 @@ fn dijkstra<F>(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize, fn_cost
                 
                 parent[next_node] = Some(node);
                 
-                heap.push(Reverse((next_f_cost, next_node, next_cost, next_resource)));
+                heap.push(Reverse((Ordered(next_f_cost), next_node, Ordered(next_cost), Ordered(next_resource))));
             }
         }
     }
@@ -53,8 +61,8 @@ fn dijkstra<F>(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize, fn_cost
     None
 }
 
-pub fn disjoint_algo(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, resource_limit: u32)-> Option<(Vec<usize>, u32, u32)> {
-    let mut graph_ext: Vec<Vec<(usize, u32, u32, bool)>> = graph
+pub fn disjoint_algo<W: Weight>(graph: &[Vec<(usize, W, W)>], s:usize, e:usize, resource_limit: W)-> Option<(Vec<usize>, W, W)> {
+    let mut graph_ext: Vec<Vec<(usize, W, W, bool)>> = graph
     .iter()
     .map(|adj| {
         adj.iter()
@@ -62,14 +70,14 @@ pub fn disjoint_algo(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, reso
             .collect()
     })
     .collect();
-    let mut best: Option<(Vec<usize>, u32, u32)> = None;
+    let mut best: Option<(Vec<usize>, W, W)> = None;
 
     for cost in 1..=2 {
-        let fn_cost = move |c1:u32, c2:u32|->u32{if cost == 1 {c1}else{c2}};
+        let fn_cost = move |c1:W, c2:W|->W{if cost == 1 {c1}else{c2}};
         while let Some(path) = dijkstra(&graph_ext, s, e, fn_cost) {
             block_path(&mut graph_ext, &path.0);
             if path.2 <= resource_limit                      
-            && best.as_ref().map_or(true, |b| path.1 < b.1) { 
+            && best.as_ref().is_none_or(|b| path.1 < b.1) { 
                 best = Some(path);                            
             }
         }
@@ -80,7 +88,7 @@ pub fn disjoint_algo(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, reso
 }
 
 
-fn get_path(parent: &Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>> {
+fn get_path(parent: &[Option<usize>], mut curr: usize) -> Option<Vec<usize>> {
     let mut path = Vec::new();
     path.push(curr); 
     
@@ -93,7 +101,7 @@ fn get_path(parent: &Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>>
     Some(path)
 }
 
-fn block_path(graph: &mut Vec<Vec<(usize,u32,u32,bool)>>, path: &Vec<usize>) {
+fn block_path<W>(graph: &mut [Vec<(usize,W,W,bool)>], path: &[usize]) {
     for edge in path.windows(2) {
         for e in &mut graph[edge[0]]{
             if e.0 == edge[1] {
@@ -104,7 +112,7 @@ fn block_path(graph: &mut Vec<Vec<(usize,u32,u32,bool)>>, path: &Vec<usize>) {
     }
 }
 
-fn unblock_network(graph: &mut Vec<Vec<(usize,u32,u32,bool)>>) {
+fn unblock_network<W>(graph: &mut [Vec<(usize,W,W,bool)>]) {
     for adj in graph {
         for edge in adj {
             edge.3 = false;
@@ -1,9 +1,14 @@
 use std::{cmp::Reverse, collections::BinaryHeap};
 
+use crate::weight::{Ordered, Weight};
 
+// entrada del heap de Dijkstra: (prioridad, nodo, costo acumulado, consumo acumulado)
+type Entry<W> = Reverse<(Ordered<W>, usize, Ordered<W>, Ordered<W>)>;
 
-pub fn edge_block(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, resource_limit: u32)-> Option<(Vec<usize>, u32, u32)> {
-    let mut graph_ext: Vec<Vec<(usize, u32, u32, bool)>> = graph
+
+
+pub fn edge_block<W: Weight>(graph: &[Vec<(usize, W, W)>], s:usize, e:usize, resource_limit: W)-> Option<(Vec<usize>, W, W)> {
+    let mut graph_ext: Vec<Vec<(usize, W, W, bool)>> = graph
     .iter()
     .map(|adj| {
         adj.iter()
@@ -11,16 +16,16 @@ pub fn edge_block(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, resourc
             .collect()
     })
     .collect();
-    let mut best: Option<(Vec<usize>, u32, u32)> = None;
+    let mut best: Option<(Vec<usize>, W, W)> = None;
     let mut last: Option<Vec<usize>> = None;
     
     
     while let Some(path) = dijkstra(&graph_ext, s, e) {
         block_heaviest_edge(&mut graph_ext, &path.0);
         if path.2 <= resource_limit                      
-            && best.as_ref().map_or(true, |b| path.1 < b.1) { 
+            && best.as_ref().is_none_or(|b| path.1 < b.1) { 
                 best = Some(path.clone());                            
-        } else if path.2 > resource_limit || last.as_ref().map_or(false, |b| *b == path.0) {break;}
+        } else if path.2 > resource_limit || last.as_ref().is_some_and(|b| *b == path.0) {break;}
 
         last = Some(path.0);
     }
@@ -29,21 +34,21 @@ pub fn edge_block(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, resourc
     best
 }
 
-fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize)-> Option<(Vec<usize>, u32, u32)> {
+fn dijkstra<W: Weight>(graph: &[Vec<(usize,W,W,bool)>], s:usize, e:usize)-> Option<(Vec<usize>, W, W)> {
     // Cola de prioridad para Dijkstra
-    let mut heap: BinaryHeap<Reverse<(u32, usize, u32,u32)>> = BinaryHeap::new();
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
     
     // Inicializamos con el nodo origen (costo, nodo)
-    heap.push(Reverse((0, s,0,0)));
+    heap.push(Reverse((Ordered(W::ZERO), s, Ordered(W::ZERO), Ordered(W::ZERO))));
     
-    let mut dist = vec![u32::MAX; graph.len()];
-    dist[s] = 0;
+    let mut dist = vec![W::MAX; graph.len()];
+    dist[s] = W::ZERO;
     
     // Para reconstruir el camino
     let mut parent: Vec<Option<usize>> = vec![None; graph.len()];
     
 
-    while let Some(Reverse((f_cost, node, accum_cost, accum_resource))) = heap.pop() {
+    while let Some(Reverse((Ordered(f_cost), node, Ordered(accum_cost), Ordered(accum_resource)))) = heap.pop() {
         // Si ya encontramos un mejor camino a este nodo, ignoramos
         if f_cost > dist[node] {
             continue;
@@ -57,8 +62,10 @@ fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize)-> Option<(
 
         // Exploramos los vecinos
         for &(next_node, edge_cost, edge_resource, block) in &graph[node] {
-            let next_cost = accum_cost + edge_cost;
-            let next_resource = accum_resource + edge_resource;
+            // un camino cuya suma desborda no se sigue
+            let (Some(next_cost), Some(next_resource)) = (accum_cost.checked_add(edge_cost), accum_resource.checked_add(edge_resource)) else {
+                continue;
+            };
             
             
 
@@ -68,7 +75,7 @@ fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,bool)>>, s:usize, e:usize)-> Option<(
                 
                 parent[next_node] = Some(node);
                 
-                heap.push(Reverse((next_resource, next_node, next_cost, next_resource)));
+                heap.push(Reverse((Ordered(next_resource), next_node, Ordered(next_cost), Ordered(next_resource))));
             }
         }
     }
@@ -91,15 +98,15 @@ fn get_path(parent: Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>> {
 }
 
 /// Marca como bloqueado únicamente el arco de costo más alto de `path`.
-fn block_heaviest_edge(
-    graph: &mut Vec<Vec<(usize, u32, u32, bool)>>,
+fn block_heaviest_edge<W: Weight>(
+    graph: &mut [Vec<(usize, W, W, bool)>],
     path: &[usize],
 ) {
     // 1.  Buscar el (u,v) con mayor costo dentro del camino.
-    let mut max_cost = 0;
+    let mut max_cost = W::ZERO;
     let mut max_from = None;
     let mut max_to   = None;
-    let mut consumed = 0;
+    let mut consumed = W::ZERO;
 
     for uv in path.windows(2) {
         let (u, v) = (uv[0], uv[1]);
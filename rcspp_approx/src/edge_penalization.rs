@@ -1,10 +1,15 @@
 use std::{cmp::Reverse, collections::BinaryHeap};
 
+use crate::weight::{Ordered, Weight};
+
+// entrada del heap de Dijkstra: (prioridad, nodo, costo acumulado, consumo acumulado)
+type Entry<W> = Reverse<(Ordered<f64>, usize, Ordered<W>, Ordered<W>)>;
+
 const KAPPA: u32 = 2;
 const P_MAX: u32 = 1000000;
 
-pub fn edge_penalization(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize, resource_limit: u32)-> Option<(Vec<usize>, u32, u32)> {
-    let mut graph_ext: Vec<Vec<(usize, u32, u32, u32)>> = graph
+pub fn edge_penalization<W: Weight>(graph: &[Vec<(usize, W, W)>], s:usize, e:usize, resource_limit: W)-> Option<(Vec<usize>, W, W)> {
+    let mut graph_ext: Vec<Vec<(usize, W, W, u32)>> = graph
     .iter()
     .map(|adj| {
         adj.iter()
@@ -12,14 +17,14 @@ pub fn edge_penalization(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize,
             .collect()
     })
     .collect();
-    let mut best: Option<(Vec<usize>, u32, u32)> = None;
+    let mut best: Option<(Vec<usize>, W, W)> = None;
 
     
     
     while let Some(path) = dijkstra(&graph_ext, s, e) {
         penalize_or_block_heaviest_edge(&mut graph_ext, &path.0);
         if path.2 <= resource_limit                      
-            && best.as_ref().map_or(true, |b| path.1 < b.1) { 
+            && best.as_ref().is_none_or(|b| path.1 < b.1) { 
                 best = Some(path);                            
         } else if path.2 > resource_limit{break;}
     }
@@ -28,23 +33,24 @@ pub fn edge_penalization(graph: &Vec<Vec<(usize, u32, u32)>>, s:usize, e:usize,
     best
 }
 
-fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,u32)>>, s:usize, e:usize)-> Option<(Vec<usize>, u32, u32)> {
-    // Cola de prioridad para Dijkstra
-    let mut heap: BinaryHeap<Reverse<(u32, usize, u32,u32)>> = BinaryHeap::new();
+fn dijkstra<W: Weight>(graph: &[Vec<(usize,W,W,u32)>], s:usize, e:usize)-> Option<(Vec<usize>, W, W)> {
+    // Cola de prioridad para Dijkstra; la prioridad (consumo por penalización) va en `f64`
+    // para que la multiplicación no desborde
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
     
     // Inicializamos con el nodo origen (costo, nodo)
-    heap.push(Reverse((0, s,0,0)));
+    heap.push(Reverse((Ordered(0.0), s, Ordered(W::ZERO), Ordered(W::ZERO))));
     
-    let mut dist = vec![u32::MAX; graph.len()];
-    dist[s] = 0;
+    let mut dist = vec![W::MAX; graph.len()];
+    dist[s] = W::ZERO;
     
     // Para reconstruir el camino
     let mut parent: Vec<Option<usize>> = vec![None; graph.len()];
     
 
-    while let Some(Reverse((f_cost, node, accum_cost, accum_resource))) = heap.pop() {
+    while let Some(Reverse((Ordered(f_cost), node, Ordered(accum_cost), Ordered(accum_resource)))) = heap.pop() {
         // Si ya encontramos un mejor camino a este nodo, ignoramos
-        if f_cost > dist[node] {
+        if f_cost > dist[node].to_f64() {
             continue;
         }
 
@@ -56,8 +62,10 @@ fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,u32)>>, s:usize, e:usize)-> Option<(V
 
         // Exploramos los vecinos
         for &(next_node, edge_cost, edge_resource, pen) in &graph[node] {
-            let next_cost = accum_cost + edge_cost;
-            let next_resource = accum_resource + edge_resource;
+            // un camino cuya suma desborda no se sigue
+            let (Some(next_cost), Some(next_resource)) = (accum_cost.checked_add(edge_cost), accum_resource.checked_add(edge_resource)) else {
+                continue;
+            };
             
             
 
@@ -67,7 +75,7 @@ fn dijkstra(graph: &Vec<Vec<(usize,u32,u32,u32)>>, s:usize, e:usize)-> Option<(V
                 
                 parent[next_node] = Some(node);
                 
-                heap.push(Reverse(((next_resource.saturating_mul(pen)), next_node, next_cost, next_resource)));
+                heap.push(Reverse((Ordered(next_resource.to_f64() * pen as f64), next_node, Ordered(next_cost), Ordered(next_resource))));
             }
         }
     }
@@ -90,15 +98,15 @@ fn get_path(parent: Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>> {
 }
 
 /// Marca como bloqueado únicamente el arco de costo más alto de `path`.
-fn penalize_or_block_heaviest_edge(
-    graph: &mut Vec<Vec<(usize, u32, u32, u32)>>,
+fn penalize_or_block_heaviest_edge<W: Weight>(
+    graph: &mut [Vec<(usize, W, W, u32)>],
     path: &[usize],
 ) {
     // 1.  Buscar el (u,v) con mayor costo dentro del camino.
-    let mut max_cost = 0;
+    let mut max_cost = W::ZERO;
     let mut max_from = None;
     let mut max_to   = None;
-    let mut consumed = 0;
+    let mut consumed = W::ZERO;
 
     for uv in path.windows(2) {
         let (u, v) = (uv[0], uv[1]);
@@ -0,0 +1,235 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, VecDeque}};
+
+use crate::larac::larac;
+use crate::mult_obj_approach::dijkstra_with_tracking;
+use crate::pulse_algorithm::{Budget, PulseStatus, BUDGET_CHECK_INTERVAL};
+use crate::weight::{Ordered, Weight};
+
+// se ajustan las cotas hasta que `superior <= RATIO_TARGET · inferior`
+const RATIO_TARGET: f64 = 8.0;
+
+// entrada del heap: (costo escalado, consumo, nodo, label permanente del que viene, costo real)
+type Entry<W> = Reverse<(u64, Ordered<W>, usize, usize, Ordered<W>)>;
+
+/// Camino factible de costo a lo sumo (1 + ε)·OPT.
+#[derive(Debug, Clone)]
+pub struct FptasResult<W> {
+    pub path: Vec<usize>,
+    pub cost: W,
+    pub consumption: W,
+    /// cota inferior de OPT con la que se escalaron los costos
+    pub lower_bound: f64,
+    /// `Optimal` si vale la garantía; `Interrupted` si se acabó el presupuesto y solo
+    /// se devuelve el mejor camino conocido (el de LARAC o el de una prueba)
+    pub status: PulseStatus,
+}
+
+/// Esquema de aproximación totalmente polinomial (FPTAS) al estilo de Hassin y Lorenz–Raz.
+///
+/// Con una cota inferior `L` de OPT, los costos se escalan a `⌊costo / θ⌋` con
+/// `θ = ε·L / h` y se busca el camino factible de menor costo escalado. Ese camino no cuesta
+/// más escalado que el óptimo y su redondeo suma menos de θ por arco, así que basta que `h`
+/// acote los arcos del camino devuelto: `h` arranca en la menor cantidad de arcos de `s` a
+/// `e` y, si el camino hallado tiene más, se repite la búsqueda con `h` igual a esos arcos.
+/// Así θ no depende del tamaño del grafo sino del largo de los caminos que se encuentran.
+/// Las cotas iniciales salen de LARAC y se acercan con pruebas de ε = 1 hasta que difieren
+/// en un factor constante, así la cantidad de costos escalados distintos es polinomial.
+pub fn fptas<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, epsilon: f64, budget: &Budget) -> Option<FptasResult<W>> {
+    let initial = larac(graph, s, e, resource_limit)?;
+    let mut best = (initial.path, initial.cost, initial.consumption);
+    let mut lower = initial.lower_bound.max(0.0);
+    let finish = |best: (Vec<usize>, W, W), lower_bound: f64, status| {
+        let (path, cost, consumption) = best;
+        Some(FptasResult {path, cost, consumption, lower_bound, status})
+    };
+    if best.1.to_f64() <= lower {
+        return finish(best, lower, PulseStatus::Optimal);
+    }
+
+    if lower <= 0.0 {
+        // OPT = 0 solo si hay un camino factible por arcos de costo cero
+        let zero_cost = dijkstra_with_tracking(graph, s, e, |cost: W, resource: W| {
+            if cost == W::ZERO {resource.to_f64()} else {f64::INFINITY}
+        });
+        if let Some(path) = zero_cost.filter(|path| path.2 <= resource_limit) {
+            return finish(path, 0.0, PulseStatus::Optimal);
+        }
+        // si no, todo camino factible usa algún arco de costo positivo
+        lower = graph.iter().flatten()
+            .map(|&(_v, cost, _cons)| cost.to_f64())
+            .filter(|&cost| cost > 0.0)
+            .fold(f64::INFINITY, f64::min);
+    }
+
+    // hay camino: LARAC encontró uno
+    let mut hops = min_hops(graph, s, e).unwrap_or(1).max(1);
+    // `scaled_search` con θ = `error / hops` y costos escalados hasta ⌊`target` / θ⌋: si el
+    // camino hallado tiene más de `hops` arcos su redondeo podría pasar de `error`, así que
+    // se sube `hops` a esos arcos y se repite
+    let mut search = |error: f64, target: f64| loop {
+        let theta = error / hops as f64;
+        match scaled_search(graph, s, e, resource_limit, theta, (target / theta).floor() as u64, budget)? {
+            Some((path, _, _)) if path.len() - 1 > hops => hops = path.len() - 1,
+            path => return Ok(path),
+        }
+    };
+    // prueba de Lorenz–Raz con ε = 1: si OPT <= V se encuentra un camino de costo <= 2V
+    while best.1.to_f64() > RATIO_TARGET * lower {
+        let v = (lower * best.1.to_f64()).sqrt();
+        match search(v, v) {
+            Err(()) => return finish(best, lower, PulseStatus::Interrupted),
+            // cuesta a lo sumo 2V, menos que la cota superior mientras esta pase de 4 veces la inferior
+            Ok(Some(path)) => best = path,
+            // ni siquiera el óptimo cabe: OPT > V
+            Ok(None) => lower = v,
+        }
+    }
+
+    match search(epsilon * lower, best.1.to_f64()) {
+        Err(()) => finish(best, lower, PulseStatus::Interrupted),
+        Ok(path) => {
+            if let Some(path) = path.filter(|path| path.1 < best.1) {
+                best = path;
+            }
+            finish(best, lower, PulseStatus::Optimal)
+        }
+    }
+}
+
+/// Menor cantidad de arcos de un camino de `s` a `e` (búsqueda en anchura), si hay camino.
+fn min_hops<W>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize) -> Option<usize> {
+    let mut distance = vec![usize::MAX; graph.len()];
+    distance[s] = 0;
+    let mut queue = VecDeque::from([s]);
+    while let Some(u) = queue.pop_front() {
+        for &(v, ..) in &graph[u] {
+            if distance[v] == usize::MAX {
+                distance[v] = distance[u] + 1;
+                if v == e {
+                    return Some(distance[v]);
+                }
+                queue.push_back(v);
+            }
+        }
+    }
+    None
+}
+
+/// Camino factible de menor costo escalado `⌊costo / theta⌋`, si no pasa de `max_scaled`.
+///
+/// Es la misma búsqueda por fijación de labels de `one_to_all`, pero ordenada por costo
+/// escalado: un label solo sirve si consume menos que los anteriores de su nodo, así que
+/// cada nodo tiene a lo sumo un label por valor de costo escalado. `Err` si se acaba el presupuesto.
+fn scaled_search<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, theta: f64, max_scaled: u64, budget: &Budget) -> Result<Option<(Vec<usize>, W, W)>, ()> {
+    let scaled = |cost: W| (cost.to_f64() / theta).floor() as u64;
+    // (nodo, label anterior) de cada label permanente, como en `one_to_all`
+    let mut predecessors: Vec<(usize, usize)> = Vec::new();
+    let mut min_consumption = vec![W::MAX; graph.len()];
+
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
+    heap.push(Reverse((0, Ordered(W::ZERO), s, 0, Ordered(W::ZERO))));
+    let mut iterations: u32 = 0;
+
+    while let Some(Reverse((scaled_cost, Ordered(consumption), node, parent, Ordered(cost)))) = heap.pop() {
+        iterations = iterations.wrapping_add(1);
+        if iterations.is_multiple_of(BUDGET_CHECK_INTERVAL) && budget.exhausted() {
+            return Err(());
+        }
+        if consumption >= min_consumption[node] {
+            continue;
+        }
+        min_consumption[node] = consumption;
+        let label = predecessors.len();
+        predecessors.push((node, if node == s {label} else {parent}));
+
+        if node == e {
+            let mut path = vec![e];
+            let mut label = label;
+            while predecessors[label].1 != label {
+                label = predecessors[label].1;
+                path.push(predecessors[label].0);
+            }
+            path.reverse();
+            return Ok(Some((path, cost, consumption)));
+        }
+
+        for &(v, edge_cost, edge_consumption) in &graph[node] {
+            // una suma que desborda no cabe en el límite ni en `max_scaled`
+            let (Some(new_consumption), Some(new_cost)) = (consumption.checked_add(edge_consumption), cost.checked_add(edge_cost)) else { continue };
+            let new_scaled = scaled_cost.saturating_add(scaled(edge_cost));
+            if new_consumption > resource_limit || new_consumption >= min_consumption[v] || new_scaled > max_scaled {
+                continue;
+            }
+            heap.push(Reverse((new_scaled, Ordered(new_consumption), v, label, Ordered(new_cost))));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{from_edges, is_feasible, limits, optimum, random_graph};
+
+    #[test]
+    fn stays_within_epsilon_when_larac_does_not() {
+        // LARAC devuelve 0 → 2 → 3 (costo 6) con una brecha; el óptimo con límite 3 es 0 → 4 → 3 (5)
+        let graph = from_edges(5, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1), (0, 4, 4, 2), (4, 3, 1, 1)]);
+        let result = fptas(&graph, 0, 3, 3, 0.1, &Budget::unlimited()).unwrap();
+        assert_eq!((result.path, result.cost, result.status), (vec![0, 4, 3], 5, PulseStatus::Optimal));
+        assert!(fptas(&graph, 0, 3, 1, 0.1, &Budget::unlimited()).is_none());
+    }
+
+    #[test]
+    fn zero_cost_paths_are_exact() {
+        let graph = from_edges(3, &[(0, 2, 0, 5), (0, 1, 0, 1), (1, 2, 0, 1), (0, 2, 3, 0)]);
+        let result = fptas(&graph, 0, 2, 2, 0.5, &Budget::unlimited()).unwrap();
+        assert_eq!((result.path, result.cost, result.lower_bound), (vec![0, 1, 2], 0, 0.0));
+        let result = fptas(&graph, 0, 2, 1, 0.5, &Budget::unlimited()).unwrap();
+        assert_eq!(result.cost, 3);
+    }
+
+    #[test]
+    fn within_epsilon_when_the_graph_is_much_longer_than_the_paths() {
+        // un camino lateral de `CHAIN` nodos que no consume: el grafo tiene cientos de nodos
+        // pero los caminos del grafo al azar tienen pocos arcos, salvo el lateral mismo
+        const CHAIN: usize = 300;
+        for seed in 0..10 {
+            let mut graph = random_graph(seed, 8, 0.4);
+            graph.extend((0..CHAIN).map(|i| vec![(8 + i + 1, 1, 0)]));
+            graph[0].push((8, 1, 0));
+            graph[8 + CHAIN - 1] = vec![(7, 1, 0)];
+            for limit in limits(&graph, 0, 7) {
+                let opt = optimum(&graph, 0, 7, limit).unwrap();
+                for epsilon in [0.01, 0.1, 0.5] {
+                    let result = fptas(&graph, 0, 7, limit, epsilon, &Budget::unlimited()).unwrap();
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    assert!(is_feasible(&graph, 0, 7, limit, &result.path, result.cost, result.consumption));
+                    assert!(result.cost as f64 <= (1.0 + epsilon) * opt as f64, "semilla {} límite {} ε {}", seed, limit, epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn within_epsilon_of_the_optimum() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                for epsilon in [0.01, 0.1, 0.5] {
+                    let result = fptas(&graph, 0, 7, limit, epsilon, &Budget::unlimited());
+                    let Some(opt) = optimum(&graph, 0, 7, limit) else {
+                        assert!(result.is_none(), "semilla {} límite {}", seed, limit);
+                        continue;
+                    };
+                    let result = result.unwrap();
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    assert!(is_feasible(&graph, 0, 7, limit, &result.path, result.cost, result.consumption));
+                    assert!(result.cost as f64 <= (1.0 + epsilon) * opt as f64, "semilla {} límite {} ε {}", seed, limit, epsilon);
+                    assert!(result.lower_bound <= opt as f64 + 1e-6);
+                }
+            }
+        }
+    }
+}
@@ -1,59 +1,112 @@
 use std::collections::{HashMap, BinaryHeap, HashSet};
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+use std::str::FromStr;
 use rand::prelude::*;
+use rand::rngs::StdRng;
 
-// Estructura para representar el grafo
-pub struct Graph {
-    pub num_nodes: usize,
-    pub edges: HashMap<usize, Vec<Edge>>,
-}
+use crate::pulse_algorithm::Budget;
+use crate::weight::{Ordered, Weight};
 
-pub struct Edge {
-    pub to: usize,
-    pub cost: i32,
-    pub resources: Vec<i32>,
-}
+// entrada del heap del Dijkstra de la codificación por camino: (peso combinado, nodo)
+type Entry = Reverse<(Ordered<f64>, usize)>;
+
+// (nodo anterior, arco por el que se llegó, consumo acumulado) en el Dijkstra restringido
+type Parent<W> = (usize, (usize, W, W), W);
+
+// cada peso de arco se multiplica por un factor al azar en [1, 1 + PERTURBATION) al
+// generar la población inicial y al mutar, para que los caminos no salgan todos iguales
+const PERTURBATION: f64 = 0.5;
+
+// intentos de reparar un hijo infactible antes de reemplazarlo por el de mínimo consumo
+const REPAIR_ATTEMPTS: usize = 5;
 
 // Estructura para representar un cromosoma
 #[derive(Clone)]
 struct Chromosome {
-    // Permutación de nodos intermedios (1 a n-2)
+    // Permutación de los nodos intermedios (todos menos el origen y el destino)
     genes: Vec<usize>,
-    fitness: i32,
+    fitness: Fitness,
+}
+
+// Aptitud de un cromosoma; mayor es mejor. Los caminos factibles van siempre antes que los
+// infactibles y, dentro de cada grupo, gana el de menor `value`: el costo si es factible y,
+// si no, el costo más la penalización por el exceso de consumo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fitness {
+    feasible: bool,
+    value: f64,
+}
+
+impl Fitness {
+    // sin camino (o sin evaluar): peor que cualquier camino
+    const NONE: Fitness = Fitness {feasible: false, value: f64::INFINITY};
+
+    // sin penalización los infactibles quedan todos empatados con `NONE`
+    fn new<W: Weight>(cost: W, consumption: W, resource_limit: W, penalty: Option<f64>) -> Self {
+        if consumption <= resource_limit {
+            return Fitness {feasible: true, value: cost.to_f64()};
+        }
+        match penalty {
+            Some(penalty) => {
+                let overrun = consumption.to_f64() - resource_limit.to_f64();
+                Fitness {feasible: false, value: cost.to_f64() + penalty * overrun}
+            }
+            None => Fitness::NONE,
+        }
+    }
+}
+
+impl Eq for Fitness {}
+
+impl Ord for Fitness {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.feasible.cmp(&other.feasible)
+            .then_with(|| other.value.total_cmp(&self.value))
+    }
+}
+
+impl PartialOrd for Fitness {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 // Estado para el algoritmo A*
 #[derive(Clone, PartialEq)]
-struct State {
+struct State<W> {
     node: usize,
-    cost: i32,
-    resources: Vec<i32>,
+    cost: W,
+    resource: W,
     priority: usize, // Basado en la permutación del cromosoma
+    parent: usize, // label desde el que se llegó
 }
 
-impl Eq for State {}
+impl<W: Weight> Eq for State<W> {}
 
 // Implementamos Ord para que funcione con BinaryHeap
-impl Ord for State {
+impl<W: Weight> Ord for State<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         // Primero por prioridad, luego por costo
         other.priority.cmp(&self.priority)
-            .then_with(|| other.cost.cmp(&self.cost))
+            .then_with(|| other.cost.total_cmp(&self.cost))
     }
 }
 
-impl PartialOrd for State {
+impl<W: Weight> PartialOrd for State<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
 // Decodifica el cromosoma usando A* con prioridades basadas en la permutación
-fn decode_chromosome(
-    graph: &Graph, 
-    chromosome: &Chromosome, 
-    resource_limits: &[i32]
-) -> Option<(Vec<usize>, i32, Vec<i32>)> {
+fn decode_chromosome<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    e: usize,
+    chromosome: &Chromosome,
+    resource_limit: W
+) -> Option<(Vec<usize>, W, W)> {
     // Creamos un mapa de prioridades basado en la permutación
     let mut priorities = HashMap::new();
     for (i, &node) in chromosome.genes.iter().enumerate() {
@@ -61,73 +114,62 @@ fn decode_chromosome(
     }
     
     let mut queue = BinaryHeap::new();
-    let mut visited = HashSet::new();
-    let mut came_from = HashMap::new();
-    let mut cost_so_far = HashMap::new();
+    // consumos de los labels ya sacados en cada nodo
+    let mut settled: HashMap<usize, Vec<W>> = HashMap::new();
+    // (nodo, label anterior) de cada label sacado; el de `s` apunta a sí mismo
+    let mut labels: Vec<(usize, usize)> = Vec::new();
 
-    
-    // Nodo inicial (0)
+    // Nodo inicial
     queue.push(State {
-        node: 0,
-        cost: 0,
-        resources: vec![0; resource_limits.len()],
+        node: s,
+        cost: W::ZERO,
+        resource: W::ZERO,
         priority: 0,
+        parent: 0,
     });
     
-    cost_so_far.insert(0, 0);
-    
     while let Some(current) = queue.pop() {
-        // Si llegamos al destino (n-1)
-        if current.node == graph.num_nodes - 1 {
+        // Si ya visitamos este nodo con estos recursos o mejores, continuamos: una llegada
+        // posterior solo sirve si consume menos. Así el camino sigue elemental, porque volver
+        // a un nodo del camino consume al menos lo mismo que la primera vez
+        let resources = settled.entry(current.node).or_default();
+        if resources.iter().any(|&resource| resource <= current.resource) {
+            continue;
+        }
+        resources.push(current.resource);
+        let label = labels.len();
+        labels.push((current.node, if current.node == s {label} else {current.parent}));
+
+        // Si llegamos al destino
+        if current.node == e {
             // Reconstruir el camino
             let mut path = vec![current.node];
-            let mut node = current.node;
+            let mut label = label;
             
-            while let Some(&prev) = came_from.get(&node) {
-                path.push(prev);
-                node = prev;
+            while labels[label].1 != label {
+                label = labels[label].1;
+                path.push(labels[label].0);
             }
             
             path.reverse();
-            return Some((path, current.cost, current.resources));
-        }
-        
-        // Si ya visitamos este nodo con estos recursos o mejores, continuamos
-        if visited.contains(&current.node) {
-            continue;
+            return Some((path, current.cost, current.resource));
         }
         
-        visited.insert(current.node);
-        
         // Exploramos vecinos
-        if let Some(edges) = graph.edges.get(&current.node) {
-            for edge in edges {
-                // Verificamos restricciones de recursos
-                let mut new_resources = current.resources.clone();
-                let mut valid = true;
-                
-                for i in 0..resource_limits.len() {
-                    new_resources[i] += edge.resources[i];
-                    if new_resources[i] > resource_limits[i] {
-                        valid = false;
-                        break;
-                    }
-                }
-                
-                if valid {
-                    let new_cost = current.cost + edge.cost;
-                    let new_priority = *priorities.get(&edge.to).unwrap_or(&usize::MAX);
-                    
-                    queue.push(State {
-                        node: edge.to,
-                        cost: new_cost,
-                        resources: new_resources,
-                        priority: new_priority,
-                    });
-                    
-                    came_from.insert(edge.to, current.node);
-                    cost_so_far.insert(edge.to, new_cost);
-                }
+        for &(to, edge_cost, edge_resource) in &graph[current.node] {
+            // Verificamos la restricción de recurso
+            let new_resource = current.resource.checked_add(edge_resource).filter(|&resource| resource <= resource_limit);
+
+            if let (Some(new_resource), Some(new_cost)) = (new_resource, current.cost.checked_add(edge_cost)) {
+                let new_priority = *priorities.get(&to).unwrap_or(&usize::MAX);
+
+                queue.push(State {
+                    node: to,
+                    cost: new_cost,
+                    resource: new_resource,
+                    priority: new_priority,
+                    parent: label,
+                });
             }
         }
     }
@@ -136,18 +178,18 @@ fn decode_chromosome(
 }
 
 // Genera un cromosoma aleatorio (permutación de nodos intermedios)
-fn generate_random_chromosome(num_nodes: usize, rng: &mut impl Rng) -> Chromosome {
-    let mut genes: Vec<usize> = (1..num_nodes-1).collect();
+fn generate_random_chromosome(num_nodes: usize, s: usize, e: usize, rng: &mut impl Rng) -> Chromosome {
+    let mut genes: Vec<usize> = (0..num_nodes).filter(|&v| v != s && v != e).collect();
     genes.shuffle(rng);
     
     Chromosome {
         genes,
-        fitness: 0,
+        fitness: Fitness::NONE,
     }
 }
 
 // Cruce de orden (Order Crossover - OX)
-fn crossover(parent1: &Chromosome, parent2: &Chromosome, rng: &mut impl Rng) -> Chromosome {
+fn crossover(parent1: &Chromosome, parent2: &Chromosome, num_nodes: usize, rng: &mut impl Rng) -> Chromosome {
     let n = parent1.genes.len();
     let point1 = rng.random_range(0..n);
     let point2 = rng.random_range(0..n);
@@ -156,7 +198,7 @@ fn crossover(parent1: &Chromosome, parent2: &Chromosome, rng: &mut impl Rng) ->
     
     // Inicializamos el hijo con marcadores
     let mut child_genes = vec![0; n];
-    let mut used = vec![false; n + 2]; // +2 porque los genes van de 1 a n-2
+    let mut used = vec![false; num_nodes]; // los genes son nodos del grafo
     
     // Copiamos el segmento de parent1
     for i in start..=end {
@@ -182,13 +224,13 @@ fn crossover(parent1: &Chromosome, parent2: &Chromosome, rng: &mut impl Rng) ->
     
     Chromosome {
         genes: child_genes,
-        fitness: 0,
+        fitness: Fitness::NONE,
     }
 }
 
 // Mutación (intercambio de dos posiciones aleatorias)
 fn mutate(chromosome: &mut Chromosome, mutation_rate: f64, rng: &mut impl Rng) {
-    if rng.random::<f64>() < mutation_rate {
+    if !chromosome.genes.is_empty() && rng.random::<f64>() < mutation_rate {
         let n = chromosome.genes.len();
         let i = rng.random_range(0..n);
         let j = rng.random_range(0..n);
@@ -196,36 +238,121 @@ fn mutate(chromosome: &mut Chromosome, mutation_rate: f64, rng: &mut impl Rng) {
     }
 }
 
-pub fn genetic_algorithm(
-    graph: &Graph,
-    population_size: usize,
-    generations: usize,
-    crossover_rate: f64,
-    mutation_rate: f64, 
-    resource_limits: &[i32]
-) -> Option<(Vec<usize>, i32, Vec<i32>)> {
-    let mut rng = rand::rng();
+/// Codificación de los cromosomas, elegible desde la línea de comandos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// permutación de todos los nodos intermedios, decodificada con una cola de prioridad
+    #[default]
+    Permutation,
+    /// el camino de `s` a `e` mismo
+    Path,
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "permutacion" => Ok(Encoding::Permutation),
+            "camino" => Ok(Encoding::Path),
+            _ => Err(format!("codificación desconocida: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Encoding::Permutation => write!(f, "permutacion"),
+            Encoding::Path => write!(f, "camino"),
+        }
+    }
+}
+
+/// Parámetros del algoritmo genético.
+#[derive(Debug, Clone, Copy)]
+pub struct GeneticParams {
+    pub population_size: usize,
+    pub generations: usize,
+    pub crossover_rate: f64,
+    pub mutation_rate: f64,
+    pub encoding: Encoding,
+    /// penalización por unidad de exceso de recurso; con `None` los caminos infactibles se
+    /// descartan (o se reparan) y con `Some` se conservan, siempre detrás de los factibles
+    pub penalty: Option<f64>,
+}
+
+/// Mejor camino factible de la última generación evaluada.
+#[derive(Debug, Clone)]
+pub struct GeneticResult<W> {
+    pub best: Option<(Vec<usize>, W, W)>,
+    /// generaciones completas
+    pub generations: usize,
+    /// si se acabó el presupuesto antes de hacer todas las generaciones
+    pub interrupted: bool,
+}
+
+/// Algoritmo genético con la codificación de `params`; misma semilla, misma evolución.
+///
+/// El presupuesto se mira una vez por generación: si se acaba, se devuelve el mejor de
+/// la población actual.
+pub fn genetic_algorithm<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    e: usize,
+    resource_limit: W,
+    params: &GeneticParams,
+    seed: u64,
+    budget: &Budget
+) -> GeneticResult<W> {
+    match params.encoding {
+        Encoding::Permutation => permutation_algorithm(graph, s, e, resource_limit, params, seed, budget),
+        Encoding::Path => path_algorithm(graph, s, e, resource_limit, params, seed, budget),
+    }
+}
+
+fn permutation_algorithm<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    e: usize,
+    resource_limit: W,
+    params: &GeneticParams,
+    seed: u64,
+    budget: &Budget
+) -> GeneticResult<W> {
+    let GeneticParams {population_size, generations, crossover_rate, mutation_rate, penalty, ..} = *params;
+    if population_size == 0 {
+        return GeneticResult {best: None, generations: 0, interrupted: false};
+    }
+    // misma semilla, misma evolución
+    let mut rng = StdRng::seed_from_u64(seed);
+    // con penalización el decodificador no poda por recurso y los infactibles compiten
+    let decode_limit = if penalty.is_some() {W::MAX} else {resource_limit};
+    let fitness = |chromosome: &Chromosome| match decode_chromosome(graph, s, e, chromosome, decode_limit) {
+        Some((_, cost, consumption)) => Fitness::new(cost, consumption, resource_limit, penalty),
+        None => Fitness::NONE,
+    };
     
     // Generamos población inicial
     let mut population: Vec<Chromosome> = (0..population_size)
-        .map(|_| generate_random_chromosome(graph.num_nodes, &mut rng))
+        .map(|_| generate_random_chromosome(graph.len(), s, e, &mut rng))
         .collect();
     
     // Evaluamos fitness inicial
     for chromosome in &mut population {
-        if let Some((_, cost, _)) = decode_chromosome(graph, chromosome, resource_limits) {
-            chromosome.fitness = 10000 / (cost + 1);
-        } else {
-            chromosome.fitness = 0;
-        }
+        chromosome.fitness = fitness(chromosome);
     }
     
-    for _ in 0..generations {
+    let mut completed = 0;
+    while completed < generations {
+        if budget.exhausted() {
+            break;
+        }
         // Ordenamos por fitness (descendente)
-        population.sort_by(|a, b| b.fitness.cmp(&a.fitness));
+        population.sort_by_key(|chromosome| Reverse(chromosome.fitness));
         
-        // Aplicamos elitismo (conservamos los mejores)
-        let elite_size = (population_size as f64 * 0.1) as usize;
+        // Aplicamos elitismo (conservamos los mejores, al menos el primero)
+        let elite_size = ((population_size as f64 * 0.1) as usize).max(1);
         let elite = population.iter().take(elite_size).cloned().collect::<Vec<_>>();
         
         let mut new_population = elite;
@@ -233,12 +360,12 @@ pub fn genetic_algorithm(
         // Generamos nueva población
         while new_population.len() < population_size {
             // Selección por torneo
-            let parent1 = tournament_selection(&population, 3, &mut rng);
-            let parent2 = tournament_selection(&population, 3, &mut rng);
+            let parent1 = tournament_selection(&population, 3, |chromosome| chromosome.fitness, &mut rng);
+            let parent2 = tournament_selection(&population, 3, |chromosome| chromosome.fitness, &mut rng);
             
             // Aplicamos cruce con cierta probabilidad
-            let mut child = if rng.random::<f64>() < crossover_rate {
-                crossover(&parent1, &parent2, &mut rng)
+            let mut child = if !parent1.genes.is_empty() && rng.random::<f64>() < crossover_rate {
+                crossover(&parent1, &parent2, graph.len(), &mut rng)
             } else if parent1.fitness > parent2.fitness {
                 parent1.clone()
             } else {
@@ -249,39 +376,407 @@ pub fn genetic_algorithm(
             mutate(&mut child, mutation_rate, &mut rng);
             
             // Evaluamos fitness
-            if let Some((_, cost, _)) = decode_chromosome(graph, &child, resource_limits) {
-                child.fitness = 10000 / (cost + 1);
-            } else {
-                child.fitness = 0;
-            }
+            child.fitness = fitness(&child);
             
             new_population.push(child);
         }
         
         population = new_population;
+        completed += 1;
     }
     
     // Ordenamos población final
-    population.sort_by(|a, b| b.fitness.cmp(&a.fitness));
+    population.sort_by_key(|chromosome| Reverse(chromosome.fitness));
     
-    // Devolvemos el mejor camino
-    decode_chromosome(graph, &population[0], resource_limits)
+    // Devolvemos el mejor camino, si es factible
+    let best = decode_chromosome(graph, s, e, &population[0], decode_limit)
+        .filter(|(_, _, consumption)| *consumption <= resource_limit);
+    GeneticResult {best, generations: completed, interrupted: completed < generations}
 }
 
-// Selección por torneo
-fn tournament_selection(
-    population: &[Chromosome], 
+// Selección por torneo (sirve para las dos codificaciones)
+fn tournament_selection<T: Clone>(
+    population: &[T], 
     tournament_size: usize, 
+    fitness: impl Fn(&T) -> Fitness,
     rng: &mut impl Rng
-) -> Chromosome {
+) -> T {
     let mut best = &population[rng.random_range(0..population.len())];
     
     for _ in 1..tournament_size {
         let competitor = &population[rng.random_range(0..population.len())];
-        if competitor.fitness > best.fitness {
+        if fitness(competitor) > fitness(best) {
             best = competitor;
         }
     }
     
-    (*best).clone()
-}
\ No newline at end of file
+    best.clone()
+}
+
+// ── Codificación por camino ─────────────────────────────────────────────
+
+// Cromosoma de la codificación por camino: los arcos de un camino elemental de `s` a `e`
+// (con arcos paralelos los nodos solos no dicen cuál se usó)
+#[derive(Clone)]
+struct PathChromosome<W> {
+    hops: Vec<(usize, W, W)>,
+    cost: W,
+    consumption: W,
+    fitness: Fitness,
+}
+
+impl<W: Weight> PathChromosome<W> {
+    fn new(hops: Vec<(usize, W, W)>, resource_limit: W, penalty: Option<f64>) -> Self {
+        let totals = hops.iter().try_fold((W::ZERO, W::ZERO), |(cost, consumption), &(_v, edge_cost, edge_consumption)| {
+            Some((cost.checked_add(edge_cost)?, consumption.checked_add(edge_consumption)?))
+        });
+        // si el costo o el consumo desborda, el camino queda último, como si no hubiera
+        let Some((cost, consumption)) = totals else {
+            return PathChromosome {hops, cost: W::MAX, consumption: W::MAX, fitness: Fitness::NONE};
+        };
+        let fitness = Fitness::new(cost, consumption, resource_limit, penalty);
+        PathChromosome {hops, cost, consumption, fitness}
+    }
+
+    fn nodes(&self, s: usize) -> Vec<usize> {
+        std::iter::once(s).chain(self.hops.iter().map(|edge| edge.0)).collect()
+    }
+}
+
+// El cromosoma es el camino mismo: el cruce intercambia los tramos que siguen a un nodo
+// común de los padres, la mutación cambia un tramo al azar por un desvío hallado con un
+// Dijkstra restringido y los hijos infactibles se reparan bajando su consumo (salvo que
+// haya penalización: entonces se conservan)
+fn path_algorithm<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    e: usize,
+    resource_limit: W,
+    params: &GeneticParams,
+    seed: u64,
+    budget: &Budget
+) -> GeneticResult<W> {
+    let GeneticParams {population_size, generations, crossover_rate, mutation_rate, penalty, ..} = *params;
+    if population_size == 0 {
+        return GeneticResult {best: None, generations: 0, interrupted: false};
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let evaluate = |hops| PathChromosome::new(hops, resource_limit, penalty);
+
+    // el camino de mínimo consumo: si no es factible ninguno lo es, y si no, sirve de
+    // último recurso para reparar
+    let Some(lightest) = constrained_route(graph, s, e, &HashSet::new(), resource_limit, |_cost: W, consumption: W| consumption.to_f64()) else {
+        return GeneticResult {best: None, generations: 0, interrupted: false};
+    };
+    let lightest = evaluate(lightest);
+    let scale = edge_scale(graph);
+
+    // Población inicial: Dijkstras restringidos con pesos combinados al azar
+    let mut population: Vec<PathChromosome<W>> = (0..population_size)
+        .map(|_| {
+            let weight = perturbed_weight(scale, &mut rng);
+            constrained_route(graph, s, e, &HashSet::new(), resource_limit, weight)
+                .map_or_else(|| lightest.clone(), evaluate)
+        })
+        .collect();
+
+    let mut completed = 0;
+    while completed < generations {
+        if budget.exhausted() {
+            break;
+        }
+        population.sort_by_key(|chromosome| Reverse(chromosome.fitness));
+
+        // Aplicamos elitismo (conservamos los mejores, al menos el primero)
+        let elite_size = ((population_size as f64 * 0.1) as usize).max(1);
+        let mut new_population = population.iter().take(elite_size).cloned().collect::<Vec<_>>();
+
+        while new_population.len() < population_size {
+            let parent1 = tournament_selection(&population, 3, |chromosome| chromosome.fitness, &mut rng);
+            let parent2 = tournament_selection(&population, 3, |chromosome| chromosome.fitness, &mut rng);
+
+            let crossed = if rng.random::<f64>() < crossover_rate {
+                crossover_paths(s, &parent1, &parent2, &mut rng).map(evaluate)
+            } else {
+                None
+            };
+            // sin cruce (o sin nodos en común) pasa el mejor de los padres
+            let mut child = crossed.unwrap_or(if parent1.fitness > parent2.fitness {parent1} else {parent2});
+
+            if !child.hops.is_empty() && rng.random::<f64>() < mutation_rate {
+                let segment = random_segment(child.hops.len(), &mut rng);
+                let weight = perturbed_weight(scale, &mut rng);
+                if let Some(mutated) = reroute(graph, s, &child, segment, resource_limit, weight) {
+                    child = evaluate(mutated);
+                }
+            }
+
+            if penalty.is_none() {
+                repair(graph, s, &mut child, resource_limit, &lightest, &mut rng);
+            }
+            new_population.push(child);
+        }
+
+        population = new_population;
+        completed += 1;
+    }
+
+    // el elitismo conserva el mejor, que es factible porque el de mínimo consumo lo es,
+    // salvo que su costo desborde
+    population.sort_by_key(|chromosome| Reverse(chromosome.fitness));
+    let best = &population[0];
+    let best = best.fitness.feasible.then(|| (best.nodes(s), best.cost, best.consumption));
+    GeneticResult {best, generations: completed, interrupted: completed < generations}
+}
+
+// (costo, consumo) del arco más caro y del que más consume, para normalizar los pesos combinados
+fn edge_scale<W: Weight>(graph: &[Vec<(usize, W, W)>]) -> (f64, f64) {
+    let (cost, consumption) = graph.iter().flatten().fold((0.0, 0.0), |(cost, consumption): (f64, f64), &(_v, edge_cost, edge_consumption)| {
+        (cost.max(edge_cost.to_f64()), consumption.max(edge_consumption.to_f64()))
+    });
+    (if cost > 0.0 {cost} else {1.0}, if consumption > 0.0 {consumption} else {1.0})
+}
+
+// peso `λ·costo + (1 - λ)·consumo` (normalizados) con λ al azar y ruido en cada arco
+fn perturbed_weight<'a, W: Weight, R: Rng>(scale: (f64, f64), rng: &'a mut R) -> impl FnMut(W, W) -> f64 + 'a {
+    let lambda: f64 = rng.random();
+    move |cost: W, consumption: W| {
+        let combined = lambda * cost.to_f64() / scale.0 + (1.0 - lambda) * consumption.to_f64() / scale.1;
+        combined * rng.random_range(1.0..1.0 + PERTURBATION)
+    }
+}
+
+// posiciones `i < j` de dos nodos de un camino de `hops` arcos
+fn random_segment(hops: usize, rng: &mut impl Rng) -> (usize, usize) {
+    let i = rng.random_range(0..hops);
+    let j = rng.random_range(i + 1..=hops);
+    (i, j)
+}
+
+// Cruce en un nodo común: el principio de un padre hasta ese nodo y el final del otro
+// desde ahí; `None` si los padres no comparten nodos intermedios
+fn crossover_paths<W: Weight>(
+    s: usize,
+    parent1: &PathChromosome<W>,
+    parent2: &PathChromosome<W>,
+    rng: &mut impl Rng
+) -> Option<Vec<(usize, W, W)>> {
+    let nodes1 = parent1.nodes(s);
+    let position2: HashMap<usize, usize> = parent2.nodes(s).into_iter().enumerate().map(|(j, v)| (v, j)).collect();
+    let common: Vec<(usize, usize)> = nodes1[..nodes1.len() - 1].iter().enumerate().skip(1)
+        .filter_map(|(i, v)| position2.get(v).map(|&j| (i, j)))
+        .collect();
+    let &(i, j) = common.choose(rng)?;
+
+    let hops = parent1.hops[..i].iter().chain(&parent2.hops[j..]).copied().collect();
+    Some(remove_cycles(s, hops))
+}
+
+// quita los ciclos que puede dejar el cruce (con pesos no negativos el camino no empeora)
+fn remove_cycles<W: Copy>(s: usize, hops: Vec<(usize, W, W)>) -> Vec<(usize, W, W)> {
+    // posición de cada nodo en el camino sin ciclos
+    let mut position: HashMap<usize, usize> = HashMap::from([(s, 0)]);
+    let mut result: Vec<(usize, W, W)> = Vec::with_capacity(hops.len());
+    for edge in hops {
+        if let Some(&pos) = position.get(&edge.0) {
+            // se vuelve a un nodo ya visitado: se descarta la vuelta
+            for dropped in result.drain(pos..) {
+                position.remove(&dropped.0);
+            }
+        } else {
+            result.push(edge);
+            position.insert(edge.0, result.len());
+        }
+    }
+    result
+}
+
+// Cambia el tramo entre las posiciones `i` y `j` por el camino más corto según `weight`
+// que no pasa por el resto del camino y, si el resto es factible, cabe en la holgura;
+// devuelve los arcos del camino nuevo
+fn reroute<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    chromosome: &PathChromosome<W>,
+    (i, j): (usize, usize),
+    resource_limit: W,
+    weight: impl FnMut(W, W) -> f64
+) -> Option<Vec<(usize, W, W)>> {
+    let nodes = chromosome.nodes(s);
+    // satura como el consumo del cromosoma, que queda en `W::MAX` si desborda
+    let segment = chromosome.hops[i..j].iter().fold(W::ZERO, |consumption, edge| consumption.checked_add(edge.2).unwrap_or(W::MAX));
+    let rest = chromosome.consumption.sub(segment);
+    // si el resto ya se pasa del límite, el tramo solo no lo arregla
+    let slack = resource_limit.checked_sub(rest).unwrap_or(W::MAX);
+    let blocked: HashSet<usize> = nodes[..i].iter().chain(&nodes[j + 1..]).copied().collect();
+
+    let detour = constrained_route(graph, nodes[i], nodes[j], &blocked, slack, weight)?;
+    Some(chromosome.hops[..i].iter().chain(&detour).chain(&chromosome.hops[j..]).copied().collect())
+}
+
+// Repara un hijo infactible cambiando tramos al azar por el desvío de menor consumo; si
+// no alcanza, lo reemplaza por el camino de mínimo consumo
+fn repair<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    chromosome: &mut PathChromosome<W>,
+    resource_limit: W,
+    lightest: &PathChromosome<W>,
+    rng: &mut impl Rng
+) {
+    for _ in 0..REPAIR_ATTEMPTS {
+        if chromosome.consumption <= resource_limit || chromosome.hops.is_empty() {
+            return;
+        }
+        let segment = random_segment(chromosome.hops.len(), rng);
+        if let Some(repaired) = reroute(graph, s, chromosome, segment, resource_limit, |_cost: W, consumption: W| consumption.to_f64()) {
+            *chromosome = PathChromosome::new(repaired, resource_limit, None);
+        }
+    }
+    if chromosome.consumption > resource_limit {
+        *chromosome = lightest.clone();
+    }
+}
+
+// Dijkstra de `from` a `to` por `weight(costo, consumo)` de cada arco, sin pasar por los
+// nodos bloqueados ni consumir más que `max_consumption`; devuelve los arcos del camino.
+// Con un solo label por nodo puede no encontrar un camino que cabe, pero nunca da uno que no.
+fn constrained_route<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    from: usize,
+    to: usize,
+    blocked: &HashSet<usize>,
+    max_consumption: W,
+    mut weight: impl FnMut(W, W) -> f64
+) -> Option<Vec<(usize, W, W)>> {
+    // mapas y no vectores: en grafos enormes un tramo explora solo una parte chica
+    let mut dist: HashMap<usize, f64> = HashMap::from([(from, 0.0)]);
+    let mut parent: HashMap<usize, Parent<W>> = HashMap::new();
+    let mut heap: BinaryHeap<Entry> = BinaryHeap::new();
+    heap.push(Reverse((Ordered(0.0), from)));
+
+    while let Some(Reverse((Ordered(distance), node))) = heap.pop() {
+        if distance > dist[&node] {
+            continue;
+        }
+        if node == to {
+            let mut hops = Vec::new();
+            let mut curr = to;
+            while curr != from {
+                let (prev, edge, _consumption) = parent[&curr];
+                hops.push(edge);
+                curr = prev;
+            }
+            hops.reverse();
+            return Some(hops);
+        }
+
+        let consumption = parent.get(&node).map_or(W::ZERO, |entry| entry.2);
+        for &edge in &graph[node] {
+            let (next, edge_cost, edge_consumption) = edge;
+            if next == from || blocked.contains(&next) {
+                continue;
+            }
+            let Some(next_consumption) = consumption.checked_add(edge_consumption).filter(|&c| c <= max_consumption) else { continue };
+            let next_distance = distance + weight(edge_cost, edge_consumption);
+            if dist.get(&next).is_none_or(|&old| next_distance < old) {
+                dist.insert(next, next_distance);
+                parent.insert(next, (node, edge, next_consumption));
+                heap.push(Reverse((Ordered(next_distance), next)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{from_edges, is_feasible, limits, optimum, random_graph};
+
+    fn params(encoding: Encoding, penalty: Option<f64>) -> GeneticParams {
+        GeneticParams {population_size: 16, generations: 10, crossover_rate: 0.8, mutation_rate: 0.2, encoding, penalty}
+    }
+
+    #[test]
+    fn returns_only_feasible_paths() {
+        for seed in 0..20 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                let opt = optimum(&graph, 0, 8, limit);
+                for encoding in [Encoding::Permutation, Encoding::Path] {
+                    for penalty in [None, Some(10.0)] {
+                        let params = params(encoding, penalty);
+                        let result = genetic_algorithm(&graph, 0, 8, limit, &params, seed, &Budget::unlimited());
+                        assert!(!result.interrupted);
+                        let result = result.best;
+                        if let Some((path, cost, consumption)) = &result {
+                            assert!(is_feasible(&graph, 0, 8, limit, path, *cost, *consumption), "semilla {} límite {} {:?}", seed, limit, params);
+                            assert!(opt <= Some(*cost));
+                        }
+                        // la codificación por caminos siempre tiene al de mínimo consumo
+                        if encoding == Encoding::Path {
+                            assert_eq!(result.is_some(), opt.is_some());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_path() {
+        for seed in 0..10 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                for encoding in [Encoding::Permutation, Encoding::Path] {
+                    let run = || genetic_algorithm(&graph, 0, 8, limit, &params(encoding, Some(10.0)), seed, &Budget::unlimited()).best;
+                    assert_eq!(run(), run());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_revisits_a_node_with_less_consumption() {
+        // el nodo 1 va primero en la permutación, pero llegar directo consume demasiado
+        // para seguir a 3; hay que volver a llegar a 1 por 2
+        let graph = from_edges(4, &[(0, 1, 1, 5), (0, 2, 1, 1), (2, 1, 1, 1), (1, 3, 1, 1)]);
+        let chromosome = Chromosome {genes: vec![1, 2], fitness: Fitness::NONE};
+        assert_eq!(decode_chromosome(&graph, 0, 3, &chromosome, 4), Some((vec![0, 2, 1, 3], 3, 3)));
+    }
+
+    #[test]
+    fn exhausted_budget_returns_the_initial_population() {
+        let graph = random_graph(0, 9, 0.4);
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let limit = u32::MAX / 2;
+        let opt = optimum(&graph, 0, 8, limit);
+        for encoding in [Encoding::Permutation, Encoding::Path] {
+            let result = genetic_algorithm(&graph, 0, 8, limit, &params(encoding, None), 0, &budget);
+            assert_eq!((result.generations, result.interrupted), (0, true));
+            if let Some((path, cost, consumption)) = &result.best {
+                assert!(is_feasible(&graph, 0, 8, limit, path, *cost, *consumption));
+            }
+            // sin generaciones, la de caminos igual tiene al de mínimo consumo
+            if encoding == Encoding::Path {
+                assert_eq!(result.best.is_some(), opt.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn feasible_paths_rank_first_and_penalty_ranks_by_overrun() {
+        let cheap_over = Fitness::new(1u32, 12, 10, Some(1.0));
+        let far_over = Fitness::new(1u32, 20, 10, Some(1.0));
+        let expensive_fit = Fitness::new(100u32, 10, 10, Some(1.0));
+        assert!(expensive_fit > cheap_over);
+        assert!(Fitness::new(50u32, 3, 10, None) > expensive_fit);
+        // entre infactibles gana el que menos se pasa
+        assert!(cheap_over > far_over && far_over > Fitness::NONE);
+        // sin penalización todos los infactibles empatan con `NONE`
+        assert_eq!(Fitness::new(1u32, 12, 10, None), Fitness::NONE);
+    }
+}
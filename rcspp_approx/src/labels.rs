@@ -0,0 +1,216 @@
+use std::str::FromStr;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::weight::Weight;
+
+/// Labels (costo, consumo) que el pulso guarda en cada nodo para la regla de dominancia.
+pub(crate) trait LabelStore<W> {
+    /// ¿Los labels de `node` dominan a un pulso que llega con (`cost`, `consumption`)?
+    fn is_dominated(&self, node: usize, cost: W, consumption: W) -> bool;
+
+    /// Registra en `node` un pulso que se va a propagar.
+    fn update(&mut self, node: usize, cost: W, consumption: W);
+}
+
+/// Estrategia de labels por nodo, elegible desde la línea de comandos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelStrategy {
+    /// mejor costo, mejor consumo y uno elegido al azar; poda solo si los tres dominan
+    #[default]
+    Three,
+    /// conjunto de Pareto de a lo sumo `capacity` labels; poda si alguno domina
+    Pareto { capacity: usize },
+}
+
+impl LabelStrategy {
+    /// Crea los labels vacíos; `seed` alimenta la elección aleatoria del esquema de tres labels.
+    pub(crate) fn build<W: Weight>(self, num_nodes: usize, seed: u64) -> Box<dyn LabelStore<W> + Send> {
+        match self {
+            LabelStrategy::Three => Box::new(ThreeLabels::new(num_nodes, seed)),
+            LabelStrategy::Pareto { capacity } => Box::new(ParetoLabels::new(num_nodes, capacity)),
+        }
+    }
+}
+
+// "tres" o "pareto:<capacidad>"
+impl FromStr for LabelStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "tres" => Ok(LabelStrategy::Three),
+            Some(("pareto", capacity)) => match capacity.parse() {
+                Ok(capacity) if capacity >= 2 => Ok(LabelStrategy::Pareto { capacity }),
+                _ => Err(format!("capacidad de Pareto inválida: {}", capacity)),
+            },
+            _ => Err(format!("estrategia de labels desconocida: {}", s)),
+        }
+    }
+}
+
+/// Esquema original: [mejor costo, mejor consumo, uno al azar] por nodo.
+pub(crate) struct ThreeLabels<W> {
+    labels: Vec<[Option<(W, W)>; 3]>,
+    rng: StdRng,
+}
+
+impl<W: Weight> ThreeLabels<W> {
+    fn new(num_nodes: usize, seed: u64) -> Self {
+        ThreeLabels {labels: vec![[None, None, None]; num_nodes], rng: StdRng::seed_from_u64(seed)}
+    }
+
+    // para actualizar los lables, esta es una manera, la otra es tener multiples labels por nodo
+    fn replace_first(cost: W, other: Option<(W,W)>) -> bool {
+        match other {
+            Some((other_cost, _consumption)) => cost < other_cost,
+            None => true
+        }
+    }
+
+    fn replace_second(consumption: W, other: Option<(W,W)>) -> bool {
+        match other {
+            Some((_cost, other_consumption)) => consumption < other_consumption,
+            None => true
+        }
+    }
+
+    fn replace_third(&mut self, other: Option<(W,W)>) -> bool {
+        match other {
+            Some((_cost, _consumption)) => self.rng.random_bool(0.5),
+            None => true
+        }
+    }
+}
+
+impl<W: Weight> LabelStore<W> for ThreeLabels<W> {
+    fn is_dominated(&self, node: usize, cost: W, consumption: W) -> bool {
+        let is_dom = self.labels[node].iter()
+            .flatten()
+            .filter(|&&(other_cost, other_consumption)| consumption >= other_consumption && cost >= other_cost)
+            .count();
+
+        is_dom > 2
+    }
+
+    fn update(&mut self, node: usize, cost: W, consumption: W) {
+        if Self::replace_first(cost, self.labels[node][0]) {
+            self.labels[node][0] = Some((cost, consumption))
+        }
+
+        if Self::replace_second(consumption, self.labels[node][1]) {
+            self.labels[node][1] = Some((cost, consumption))
+        }
+
+        if self.replace_third(self.labels[node][2]) {
+            self.labels[node][2] = Some((cost, consumption))
+        }
+    }
+}
+
+/// Conjunto de Pareto acotado por nodo, ordenado por costo creciente
+/// (y por lo tanto consumo decreciente).
+pub(crate) struct ParetoLabels<W> {
+    capacity: usize,
+    labels: Vec<Vec<(W, W)>>,
+}
+
+impl<W: Weight> ParetoLabels<W> {
+    fn new(num_nodes: usize, capacity: usize) -> Self {
+        ParetoLabels {capacity, labels: vec![Vec::new(); num_nodes]}
+    }
+}
+
+impl<W: Weight> LabelStore<W> for ParetoLabels<W> {
+    fn is_dominated(&self, node: usize, cost: W, consumption: W) -> bool {
+        self.labels[node].iter()
+            .any(|&(other_cost, other_consumption)| cost >= other_cost && consumption >= other_consumption)
+    }
+
+    fn update(&mut self, node: usize, cost: W, consumption: W) {
+        let labels = &mut self.labels[node];
+        if labels.iter().any(|&(other_cost, other_consumption)| cost >= other_cost && consumption >= other_consumption) {
+            return;
+        }
+        labels.retain(|&(other_cost, other_consumption)| other_cost < cost || other_consumption < consumption);
+
+        let pos = labels.partition_point(|&(other_cost, _)| other_cost < cost);
+        labels.insert(pos, (cost, consumption));
+        if labels.len() > self.capacity {
+            // lleno: se descarta el vecino interior más cercano en costo al nuevo label,
+            // así se conservan los extremos y el frente queda repartido
+            let last = labels.len() - 1;
+            let removed = [pos.checked_sub(1), Some(pos + 1)].into_iter()
+                .flatten()
+                .filter(|&i| i > 0 && i < last)
+                .min_by(|&i, &j| distance(labels[i].0, cost).total_cmp(&distance(labels[j].0, cost)))
+                .unwrap_or(pos);
+            labels.remove(removed);
+        }
+    }
+}
+
+// distancia en costo entre dos labels
+pub(crate) fn distance<W: Weight>(a: W, b: W) -> f64 {
+    (a.to_f64() - b.to_f64()).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_strategies() {
+        assert_eq!("tres".parse(), Ok(LabelStrategy::Three));
+        assert_eq!("pareto:4".parse(), Ok(LabelStrategy::Pareto { capacity: 4 }));
+        for invalid in ["pareto:1", "pareto:x", "pareto", "cuatro"] {
+            assert!(invalid.parse::<LabelStrategy>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn three_labels_prune_only_when_all_three_dominate() {
+        let mut labels = ThreeLabels::<u32>::new(1, 0);
+        labels.update(0, 5, 5);
+        assert!(labels.is_dominated(0, 6, 6));
+        // el de mejor costo ya no domina a (6, 6)
+        labels.update(0, 3, 9);
+        assert!(!labels.is_dominated(0, 6, 6));
+        assert!(labels.is_dominated(0, 6, 9));
+    }
+
+    #[test]
+    fn same_seed_same_random_label() {
+        let run = |seed| {
+            let mut labels = ThreeLabels::<u32>::new(1, seed);
+            let mut rng = StdRng::seed_from_u64(99);
+            let mut third = Vec::new();
+            for _ in 0..50 {
+                labels.update(0, rng.random_range(1..100), rng.random_range(1..100));
+                third.push(labels.labels[0][2]);
+            }
+            third
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn pareto_labels_keep_the_extremes_when_full() {
+        let mut labels = ParetoLabels::<u32>::new(1, 3);
+        for (cost, consumption) in [(5, 5), (1, 9), (9, 1)] {
+            labels.update(0, cost, consumption);
+        }
+        assert!(labels.is_dominated(0, 6, 6));
+        assert!(!labels.is_dominated(0, 4, 6));
+        // un label dominado no entra
+        labels.update(0, 6, 6);
+        assert_eq!(labels.labels[0], vec![(1, 9), (5, 5), (9, 1)]);
+        // lleno: sale el interior más cercano en costo y quedan los extremos
+        labels.update(0, 4, 6);
+        assert_eq!(labels.labels[0], vec![(1, 9), (4, 6), (9, 1)]);
+        // un label que domina a varios los reemplaza
+        labels.update(0, 1, 1);
+        assert_eq!(labels.labels[0], vec![(1, 1)]);
+    }
+}
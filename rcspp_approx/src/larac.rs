@@ -0,0 +1,128 @@
+use crate::mult_obj_approach::dijkstra_with_tracking;
+use crate::weight::Weight;
+
+// iteraciones máximas de la búsqueda de λ (LARAC termina antes salvo por redondeo)
+const MAX_ITERATIONS: usize = 100;
+
+// holgura relativa para decidir que la recta ya no corta otro camino
+const TOLERANCE: f64 = 1e-9;
+
+/// Resultado de LARAC: el mejor camino factible encontrado y la cota que certifica su calidad.
+#[derive(Debug, Clone)]
+pub struct LaracResult<W> {
+    pub path: Vec<usize>,
+    pub cost: W,
+    pub consumption: W,
+    /// multiplicador λ >= 0 que maximiza la cota dual
+    pub lambda: f64,
+    /// `min(costo + λ·recurso) - λ·límite`: ningún camino factible cuesta menos
+    pub lower_bound: f64,
+}
+
+impl<W: Weight> LaracResult<W> {
+    /// Brecha relativa entre el costo del camino y la cota inferior (0 si es óptimo probado).
+    pub fn gap(&self) -> f64 {
+        let cost = self.cost.to_f64();
+        if cost == 0.0 {
+            return 0.0;
+        }
+        ((cost - self.lower_bound) / cost).max(0.0)
+    }
+}
+
+/// LARAC (Lagrangian Relaxation Aggregated Cost) de Jüttner et al.
+///
+/// Parte del camino de mínimo costo (si es factible, es óptimo) y del de mínimo recurso
+/// (si no es factible, no hay camino). Luego toma λ como la pendiente de la recta que une
+/// ambos en el plano (recurso, costo) y busca el camino de mínimo `costo + λ·recurso`: si
+/// queda sobre la recta, λ es óptimo; si no, reemplaza al extremo del mismo lado del límite.
+pub fn larac<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W) -> Option<LaracResult<W>> {
+    let limit = resource_limit.to_f64();
+    let combined_path = |lambda: f64| {
+        dijkstra_with_tracking(graph, s, e, move |cost: W, resource: W| cost.to_f64() + lambda * resource.to_f64())
+    };
+
+    let mut cheapest = combined_path(0.0)?;
+    if cheapest.2 <= resource_limit {
+        let lower_bound = cheapest.1.to_f64();
+        let (path, cost, consumption) = cheapest;
+        return Some(LaracResult {path, cost, consumption, lambda: 0.0, lower_bound});
+    }
+    // λ enorme: en la práctica, el camino de mínimo recurso
+    let mut lightest = dijkstra_with_tracking(graph, s, e, |_cost: W, resource: W| resource.to_f64())?;
+    if lightest.2 > resource_limit {
+        return None;
+    }
+
+    let point = |path: &(Vec<usize>, W, W)| (path.1.to_f64(), path.2.to_f64());
+    let mut lambda = 0.0;
+    let mut minimum_combined = f64::NEG_INFINITY;
+    for _ in 0..MAX_ITERATIONS {
+        let (cheap_cost, cheap_res) = point(&cheapest);
+        let (light_cost, light_res) = point(&lightest);
+        lambda = (light_cost - cheap_cost) / (cheap_res - light_res);
+        let combined = |(cost, res): (f64, f64)| cost + lambda * res;
+
+        let Some(path) = combined_path(lambda) else { break };
+        minimum_combined = combined(point(&path));
+        if minimum_combined >= combined((cheap_cost, cheap_res)) - TOLERANCE * minimum_combined.abs().max(1.0) {
+            break;
+        }
+        if path.2 <= resource_limit {
+            lightest = path;
+        } else {
+            cheapest = path;
+        }
+    }
+
+    let (path, cost, consumption) = lightest;
+    // la cota dual vale para cualquier λ >= 0; nunca por encima del costo del camino factible
+    let lower_bound = (minimum_combined - lambda * limit).min(cost.to_f64());
+    Some(LaracResult {path, cost, consumption, lambda, lower_bound})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{from_edges, is_feasible, limits, optimum, random_graph};
+
+    #[test]
+    fn certifies_the_optimum_or_reports_the_gap() {
+        // puntos (costo, recurso): 0 → 3 (1, 10), 0 → 1 → 3 (4, 4), 0 → 2 → 3 (6, 2)
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+
+        let result = larac(&graph, 0, 3, 10).unwrap();
+        assert_eq!((result.path.clone(), result.lambda, result.gap()), (vec![0, 3], 0.0, 0.0));
+
+        // con límite 4 la cota dual alcanza al óptimo
+        let result = larac(&graph, 0, 3, 4).unwrap();
+        assert_eq!((result.path.clone(), result.lambda, result.lower_bound), (vec![0, 1, 3], 0.5, 4.0));
+        assert_eq!(result.gap(), 0.0);
+
+        // con límite 3 queda una brecha: λ = 1 da la cota 8 - 3 = 5 y el camino cuesta 6
+        let result = larac(&graph, 0, 3, 3).unwrap();
+        assert_eq!((result.path.clone(), result.lambda, result.lower_bound), (vec![0, 2, 3], 1.0, 5.0));
+        assert!((result.gap() - 1.0 / 6.0).abs() < 1e-9);
+
+        assert!(larac(&graph, 0, 3, 1).is_none());
+    }
+
+    #[test]
+    fn bounds_the_optimum() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                let result = larac(&graph, 0, 7, limit);
+                let Some(opt) = optimum(&graph, 0, 7, limit) else {
+                    assert!(result.is_none(), "semilla {} límite {}", seed, limit);
+                    continue;
+                };
+                let result = result.unwrap();
+                assert!(is_feasible(&graph, 0, 7, limit, &result.path, result.cost, result.consumption));
+                assert!(opt <= result.cost);
+                assert!(result.lower_bound <= opt as f64 + 1e-6, "semilla {} límite {}", seed, limit);
+                assert!(result.lambda >= 0.0 && (0.0..=1.0).contains(&result.gap()));
+            }
+        }
+    }
+}
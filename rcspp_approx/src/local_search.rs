@@ -0,0 +1,148 @@
+use crate::detour::{nodes, totals, Detour, Neighborhood};
+use crate::pulse_algorithm::{Budget, PulseStatus};
+use crate::weight::Weight;
+
+/// Camino mejorado por la búsqueda local.
+#[derive(Debug, Clone)]
+pub struct LocalSearchResult<W> {
+    pub best: (Vec<usize>, W, W),
+    /// movimientos de mejora aplicados
+    pub moves: usize,
+    /// `Optimal` si no queda ningún movimiento que mejore (óptimo local); `Interrupted`
+    /// si se acabó el presupuesto antes
+    pub status: PulseStatus,
+}
+
+/// Búsqueda local sobre un camino factible (el de cualquier heurística).
+///
+/// En cada ronda se aplica el mejor de dos tipos de movimiento que bajen el costo sin
+/// pasarse del límite de recurso:
+/// - atajo: un arco directo entre dos nodos del camino (o un arco paralelo más barato);
+/// - reemplazo de subcamino: el desvío más barato entre dos nodos del camino (a lo sumo
+///   `detour::SEGMENT_HOPS` arcos) que no pasa por el resto del camino y cabe en la holgura de
+///   recurso que queda.
+///
+/// Termina cuando no hay movimiento de mejora o se acaba el presupuesto.
+pub fn local_search<W: Weight>(graph: &[Vec<(usize, W, W)>], initial: &(Vec<usize>, W, W), resource_limit: W, budget: &Budget) -> LocalSearchResult<W> {
+    let path = &initial.0;
+    // con arcos paralelos el camino no dice cuál se usó: se arranca con el de menor consumo
+    let Some(mut edges) = path.windows(2)
+        .map(|uv| graph[uv[0]].iter().enumerate()
+            .filter(|(_index, edge)| edge.0 == uv[1])
+            .min_by(|(_, a), (_, b)| a.2.total_cmp(&b.2).then(a.1.total_cmp(&b.1)))
+            .map(|(index, _edge)| index))
+        .collect::<Option<Vec<usize>>>()
+    else {
+        return LocalSearchResult {best: initial.clone(), moves: 0, status: PulseStatus::Optimal};
+    };
+    let s = path[0];
+    let mut moves = 0;
+    let mut status = PulseStatus::Optimal;
+
+    loop {
+        if budget.exhausted() {
+            status = PulseStatus::Interrupted;
+            break;
+        }
+        let Some(best_move) = best_move(graph, s, &edges, resource_limit, budget) else { break };
+        edges.splice(best_move.from..best_move.to, best_move.edges);
+        moves += 1;
+    }
+
+    // el arranque con los arcos de menor consumo puede haber quedado peor que el original
+    let best = match totals(graph, s, &edges) {
+        Some((cost, consumption)) if cost < initial.1 => (nodes(graph, s, &edges), cost, consumption),
+        _ => initial.clone(),
+    };
+    LocalSearchResult {best, moves, status}
+}
+
+// el movimiento factible que deja el camino más barato, si alguno lo mejora
+fn best_move<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, edges: &[usize], resource_limit: W, budget: &Budget) -> Option<Detour<W>> {
+    let neighborhood = Neighborhood::new(graph, s, edges, resource_limit)?;
+    // costo del mejor camino hasta ahora: el actual o el del mejor movimiento
+    let (mut bound, _total_consumption) = neighborhood.total();
+    let mut best: Option<Detour<W>> = None;
+    let mut consider = |detour: Detour<W>, bound: &mut W| {
+        if detour.consumption <= resource_limit && detour.cost < *bound {
+            *bound = detour.cost;
+            best = Some(detour);
+        }
+    };
+
+    // atajos: arcos directos hacia un nodo más adelante del camino
+    for (from, &u) in neighborhood.path.iter().enumerate() {
+        for (index, edge) in graph[u].iter().enumerate() {
+            if let Some(to) = neighborhood.position(edge.0).filter(|&to| to > from) {
+                if let Some(detour) = neighborhood.detour(from, to, vec![index]) {
+                    consider(detour, &mut bound);
+                }
+            }
+        }
+    }
+
+    // reemplazos de subcamino
+    for from in 0..edges.len() {
+        if budget.exhausted() {
+            break;
+        }
+        for detour in neighborhood.detours(from, |_, _| false, |cost| cost < bound) {
+            consider(detour, &mut bound);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{all_paths, from_edges, is_feasible, limits, optimum, random_graph};
+
+    #[test]
+    fn improves_to_a_feasible_path() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 9, 0.4);
+            let paths = all_paths(&graph, 0, 8);
+            for limit in limits(&graph, 0, 8) {
+                let Some(opt) = optimum(&graph, 0, 8, limit) else { continue };
+                // se arranca del factible más caro y del más barato
+                let feasible = || paths.iter().filter(|path| path.2 <= limit);
+                let worst = feasible().max_by_key(|path| path.1).unwrap();
+                let best = feasible().min_by_key(|path| path.1).unwrap();
+                for initial in [worst, best] {
+                    let result = local_search(&graph, initial, limit, &Budget::unlimited());
+                    let (path, cost, consumption) = &result.best;
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    assert!(is_feasible(&graph, 0, 8, limit, path, *cost, *consumption));
+                    assert!(opt <= *cost && *cost <= initial.1, "semilla {} límite {}", seed, limit);
+                    assert_eq!(result.moves == 0, *cost == initial.1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn switches_to_a_cheaper_parallel_arc() {
+        // dos arcos 0 -> 1: el de menor consumo es el caro; con holgura conviene el otro
+        let graph = from_edges(3, &[(0, 1, 5, 1), (0, 1, 1, 3), (1, 2, 1, 1)]);
+        let result = local_search(&graph, &(vec![0, 1, 2], 6, 2), 4, &Budget::unlimited());
+        assert_eq!(result.best, (vec![0, 1, 2], 2, 4));
+        assert_eq!(result.moves, 1);
+        // sin holgura se queda con el arco caro
+        let result = local_search(&graph, &(vec![0, 1, 2], 6, 2), 3, &Budget::unlimited());
+        assert_eq!(result.best, (vec![0, 1, 2], 6, 2));
+        assert_eq!(result.moves, 0);
+    }
+
+    #[test]
+    fn exhausted_budget_keeps_the_initial_path() {
+        let graph = from_edges(4, &[(0, 1, 5, 1), (1, 3, 5, 1), (0, 3, 1, 1)]);
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = local_search(&graph, &(vec![0, 1, 3], 10, 2), 5, &budget);
+        assert_eq!(result.status, PulseStatus::Interrupted);
+        assert_eq!((result.best, result.moves), ((vec![0, 1, 3], 10, 2), 0));
+        assert_eq!(local_search(&graph, &(vec![0, 1, 3], 10, 2), 5, &Budget::unlimited()).best, (vec![0, 3], 1, 1));
+    }
+}
@@ -1,39 +1,167 @@
 use std::{
     env,
     fs::File,
-    io::{self, BufRead},
-    sync::{Arc, mpsc},
-    thread,
+    io::{self, BufRead, BufWriter, Write},
+    str::FromStr,
     time::{Duration, Instant},
 };
 
+use labels::LabelStrategy;
+use pulse_algorithm::{Budget, Pulse, PulseOptions, PulseResult, PulseStats, PulseStatus, WarmStart};
+use weight::Weight;
+
+mod ant_colony;
 mod bidirectional_pulse;
+mod detour;
+mod labels;
+mod larac;
+mod local_search;
+mod multi_resource_pulse;
+mod one_to_all;
+mod parallel_pulse;
+mod pareto_pulse;
+mod pricing_pulse;
 mod pulse_algorithm;
+mod time_window_pulse;
 mod mult_obj_approach;
 mod disjoint_path_approach;
 mod edge_blocking_algo;
 mod edge_penalization;
+mod fptas;
+mod genetic_rcsp;
+#[cfg(test)]
+mod test_graphs;
+mod weight;
+mod tabu_search;
+mod yen;
 
 fn main() -> io::Result<()> {
     // ── 1. Argumentos de línea de comandos ───────────────────────────────
     let args: Vec<String> = env::args().collect();
     if args.len() < 5 {
         eprintln!(
-            "Uso: {} <archivo_entrada> <nodo_origen> <nodo_destino> <límite_recursos>",
+            "Uso: {} <archivo_entrada> <nodo_origen> <nodo_destino> <límite_recursos> [opciones]\n\
+             Opciones:\n  \
+             --hilos <n>    hilos para el algoritmo del pulso (por defecto 1)\n  \
+             --bidireccional  se corre además el pulso bidireccional (guarda en memoria los caminos\n                 \
+             parciales de la mitad del recurso)\n  \
+             --tiempo <s>   segundos máximos para cada algoritmo del pulso (por defecto 60, 0 = sin límite)\n  \
+             --labels <e>   labels por nodo del pulso: tres | pareto:<capacidad> (por defecto tres)\n  \
+             --lagrangiana  los pulsos de un recurso podan además con la cota lagrangiana (λ de LARAC)\n  \
+             --semilla <n>  semilla de la aleatoriedad de todos los algoritmos (por defecto, una al azar)\n  \
+             --recursos <l1,l2,..>  límites de varios recursos; el archivo trae un consumo por recurso\n                 \
+             y se corre además el pulso multi-recurso\n  \
+             --ventanas <archivo>  ventanas de tiempo, una línea `nodo inferior superior` por nodo;\n                 \
+             se corre además el pulso con ventanas (el consumo de cada arco es su tiempo)\n  \
+             --duales <archivo>  duales enteros de los nodos, una línea `nodo dual`; se corre además el\n                 \
+             pricing (ESPPRC) con costos reducidos `costo - dual[u]` de cada arco (u, v)\n  \
+             --columnas <k>  columnas de costo reducido negativo que devuelve el pricing (por defecto 10)\n  \
+             --pesos <t>    tipo de los costos y consumos: u32 | u64 | f64 (por defecto u32)\n  \
+             --frente <l>   se corre además el pulso que devuelve el frente de Pareto (costo, consumo)\n                 \
+             completo con consumo a lo sumo <l>\n  \
+             --inicio <i>   incumbente inicial de los pulsos: `heuristicas` (el mejor camino de las\n                 \
+             heurísticas rápidas), `cota:<costo>` (solo se buscan caminos más baratos) o un\n                 \
+             archivo con los nodos del camino separados por espacios\n  \
+             --uno-a-todos <archivo>  se corre además la búsqueda uno a todos desde el origen y se\n                 \
+             escribe una línea `nodo costo consumo camino..` por cada nodo alcanzable\n  \
+             --epsilon <ε>  se corre además el FPTAS, que garantiza un costo a lo sumo (1 + ε)·óptimo\n  \
+             --yen <k>      se corre además Yen, que lista hasta k caminos en orden de costo hasta\n                 \
+             el primero factible\n  \
+             --busqueda-local <s>  mejora con búsqueda local (atajos y reemplazo de subcaminos)\n                 \
+             el camino de cada heurística, con a lo sumo <s> segundos cada una (0 = sin límite)\n  \
+             --genetico <n>  se corre además el algoritmo genético con una población de <n>\n  \
+             --generaciones <g>  generaciones del algoritmo genético (por defecto 100)\n  \
+             --cruce <p>    probabilidad de cruce del algoritmo genético (por defecto 0.8)\n  \
+             --mutacion <p>  probabilidad de mutación del algoritmo genético (por defecto 0.1)\n  \
+             --codificacion <c>  cromosomas del algoritmo genético: permutacion (de los nodos\n                 \
+             intermedios) | camino (el camino mismo) (por defecto permutacion)\n  \
+             --penalizacion <p>  el algoritmo genético conserva los caminos infactibles con\n                 \
+             costo + p·exceso de recurso, siempre detrás de los factibles (por defecto se descartan)\n  \
+             --hormigas <n>  se corre además la colonia de hormigas con <n> hormigas por iteración\n  \
+             --iteraciones-hormigas <i>  iteraciones de la colonia de hormigas (por defecto 100)\n  \
+             --evaporacion <ρ>  fracción de feromona que se evapora por iteración, en (0, 1] (por defecto 0.1)\n  \
+             --tabu <i>     se corre además la búsqueda tabú con a lo sumo <i> iteraciones\n  \
+             --permanencia <t>  iteraciones que un arco quitado queda tabú (por defecto 10)",
             args[0]
         );
         std::process::exit(1);
     }
 
+    // la semilla siempre se imprime para poder reproducir cualquier resultado
+    let seed: u64 = option(&args, "--semilla").unwrap_or_else(rand::random);
+    println!("Semilla: {}", seed);
+    println!();
+
+    // todo lo demás depende del tipo de los pesos
+    match option::<String>(&args, "--pesos").as_deref().unwrap_or("u32") {
+        "u32" => run::<u32>(&args, seed),
+        "u64" => run::<u64>(&args, seed),
+        "f64" => run::<f64>(&args, seed),
+        other => {
+            eprintln!("Tipo de pesos desconocido: {} (se admite u32, u64 o f64)", other);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Lee el grafo con pesos de tipo `W` y corre todos los algoritmos sobre él.
+fn run<W: Weight>(args: &[String], seed: u64) -> io::Result<()> {
     let filename = &args[1];
     let s: usize = args[2].parse().expect("Nodo origen inválido");
     let e: usize = args[3].parse().expect("Nodo destino inválido");
-    let resource_limit: u32 = args[4].parse().expect("Límite de recursos inválido");
+    let resource_limit: W = parse(&args[4], "Límite de recursos inválido");
+    let threads: usize = option(args, "--hilos").unwrap_or(1);
+    let bidirectional = flag(args, "--bidireccional");
+    let time_limit: u64 = option(args, "--tiempo").unwrap_or(60);
+    let labels: LabelStrategy = option(args, "--labels").unwrap_or_default();
+    let lagrangian = flag(args, "--lagrangiana");
+    let resource_limits: Option<Vec<W>> = option::<String>(args, "--recursos").map(|limits| {
+        limits.split(',').map(|limit| parse(limit, "Límite de recursos inválido")).collect()
+    });
+    let windows_file: Option<String> = option(args, "--ventanas");
+    let duals_file: Option<String> = option(args, "--duales");
+    let max_columns: usize = option(args, "--columnas").unwrap_or(10);
+    let front_limit: Option<W> = option::<String>(args, "--frente").map(|limit| parse(&limit, "Límite de recursos inválido"));
+    let initial: Option<String> = option(args, "--inicio");
+    let one_to_all_file: Option<String> = option(args, "--uno-a-todos");
+    let epsilon: Option<f64> = option(args, "--epsilon");
+    let yen_paths: Option<usize> = option(args, "--yen");
+    let local_search_time: Option<u64> = option(args, "--busqueda-local");
+    let genetic_params = option::<usize>(args, "--genetico").map(|population_size| genetic_rcsp::GeneticParams {
+        population_size,
+        generations: option(args, "--generaciones").unwrap_or(100),
+        crossover_rate: option(args, "--cruce").unwrap_or(0.8),
+        mutation_rate: option(args, "--mutacion").unwrap_or(0.1),
+        encoding: option(args, "--codificacion").unwrap_or_default(),
+        penalty: option(args, "--penalizacion"),
+    });
+    if epsilon.is_some_and(|epsilon| epsilon <= 0.0) {
+        eprintln!("El ε del FPTAS debe ser positivo.");
+        std::process::exit(1);
+    }
+    let ant_params = option::<usize>(args, "--hormigas").map(|colony_size| ant_colony::AntColonyParams {
+        colony_size,
+        iterations: option(args, "--iteraciones-hormigas").unwrap_or(100),
+        evaporation: option(args, "--evaporacion").unwrap_or(0.1),
+    });
+    let tabu_params = option::<usize>(args, "--tabu").map(|iterations| tabu_search::TabuParams {
+        iterations,
+        tenure: option(args, "--permanencia").unwrap_or(10),
+    });
+    if ant_params.is_some_and(|params| !(params.evaporation > 0.0 && params.evaporation <= 1.0)) {
+        eprintln!("La evaporación de la colonia de hormigas debe estar en (0, 1].");
+        std::process::exit(1);
+    }
+    if genetic_params.and_then(|params| params.penalty).is_some_and(|penalty| penalty < 0.0) {
+        eprintln!("La penalización del algoritmo genético no puede ser negativa.");
+        std::process::exit(1);
+    }
 
     // ── 2. Leer todas las aristas y detectar el número de nodos ───────────
     let file = File::open(filename)?;
     let reader = io::BufReader::new(file);
-    let mut edges: Vec<(usize, usize, u32, u32)> = Vec::new();
+    let mut edges: Vec<(usize, usize, W, W)> = Vec::new();
+    let mut multi_edges: Vec<(usize, usize, W, Vec<W>)> = Vec::new();
     let mut max_node = 0;
 
     for line in reader.lines() {
@@ -42,21 +170,37 @@ fn main() -> io::Result<()> {
             continue;
         }
         let parts: Vec<&str> = line.split_whitespace().collect();
+        // `u v costo` y un consumo por recurso
+        let resources = resource_limits.as_ref().map_or(1, |limits| limits.len());
+        if parts.len() < 3 + resources {
+            eprintln!("Consumo inválido: faltan consumos en la línea `{}` (se esperaban {})", line, resources);
+            std::process::exit(1);
+        }
         let u: usize = parts[0].parse().expect("Índice de nodo inválido");
         let v: usize = parts[1].parse().expect("Índice de nodo inválido");
-        let cost: u32 = parts[2].parse().expect("Costo inválido");
-        let cons: u32 = parts[3].parse().expect("Consumo inválido");
+        let cost: W = parse(parts[2], "Costo inválido");
+        let cons: W = parse(parts[3], "Consumo inválido");
 
         edges.push((u, v, cost, cons));
         max_node = max_node.max(u).max(v);
+
+        if resource_limits.is_some() {
+            let resources: Vec<W> = parts[3..3 + resources].iter()
+                .map(|r| parse(r, "Consumo inválido"))
+                .collect();
+            multi_edges.push((u, v, cost, resources));
+        }
     }
 
-    // ── 3. Construir la lista de adyacencia y compartir con Arc ───────────
-    let mut graph: Vec<Vec<(usize, u32, u32)>> = vec![Vec::new(); max_node + 1];
+    // ── 3. Construir la lista de adyacencia ──────────────────────────────
+    let mut graph: Vec<Vec<(usize, W, W)>> = vec![Vec::new(); max_node + 1];
     for (u, v, cost, cons) in edges {
         graph[u].push((v, cost, cons));
     }
-    let graph = Arc::new(graph);
+    let mut multi_graph: Vec<Vec<multi_resource_pulse::MultiEdge<W>>> = vec![Vec::new(); max_node + 1];
+    for (u, v, cost, resources) in multi_edges {
+        multi_graph[u].push((v, cost, resources));
+    }
 
     if s >= graph.len() || e >= graph.len() {
         eprintln!(
@@ -66,90 +210,565 @@ fn main() -> io::Result<()> {
         std::process::exit(1);
     }
 
+    // nodos sin ventana: [0, ∞)
+    let mut windows: Vec<(W, W)> = vec![(W::ZERO, W::MAX); graph.len()];
+    if let Some(windows_file) = &windows_file {
+        for line in io::BufReader::new(File::open(windows_file)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 3 {
+                eprintln!("Ventana inválida: se esperaba `nodo inferior superior` en la línea `{}`", line);
+                std::process::exit(1);
+            }
+            let v: usize = parts[0].parse().expect("Índice de nodo inválido");
+            if v >= windows.len() {
+                eprintln!("El nodo {} de las ventanas está fuera de rango (0..{}).", v, windows.len() - 1);
+                std::process::exit(1);
+            }
+            let lower: W = parse(parts[1], "Ventana inválida");
+            let upper: W = parse(parts[2], "Ventana inválida");
+            windows[v] = (lower, upper);
+        }
+    }
+
+    // ── 3b. Incumbente inicial para los pulsos, si se pidió ──────────────
+    let warm_start = match initial.as_deref() {
+        None => None,
+        Some("heuristicas") => {
+            let start = Instant::now();
+            let best = heuristic_incumbent(&graph, s, e, resource_limit);
+            match &best {
+                Some((path, cost, _)) => println!("Incumbente inicial (heurísticas): {:?}\nCosto total: {}", path, cost),
+                None => println!("Las heurísticas no encontraron un camino factible para arrancar."),
+            }
+            println!("Duración: {:?}", start.elapsed());
+            println!();
+            best.map(|(path, _, _)| WarmStart::Path(path))
+        }
+        Some(bound) if bound.starts_with("cota:") => {
+            let bound: W = parse(&bound["cota:".len()..], "Cota inválida");
+            println!("Cota superior inicial: {} (los pulsos solo reportan caminos más baratos)", bound);
+            println!();
+            Some(WarmStart::Bound(bound))
+        }
+        Some(path_file) => {
+            let mut path: Vec<usize> = Vec::new();
+            for line in io::BufReader::new(File::open(path_file)?).lines() {
+                path.extend(line?.split_whitespace().map(|v| parse::<usize>(v, "Índice de nodo inválido")));
+            }
+            println!("Incumbente inicial (archivo): {:?}", path);
+            println!();
+            Some(WarmStart::Path(path))
+        }
+    };
+    // con `cota:<costo>` que no aparezca camino no dice que no haya caminos factibles
+    let cost_bound = match &warm_start {
+        Some(WarmStart::Bound(bound)) => Some(*bound),
+        _ => None,
+    };
+    let pulse_options = || PulseOptions {
+        budget: if time_limit == 0 {Budget::unlimited()} else {Budget::timeout(Duration::from_secs(time_limit))},
+        labels,
+        seed,
+        warm_start: warm_start.clone(),
+        lagrangian,
+    };
+
     let mut pulse_cost = f64::MAX;
     let mut curr_cost = f64::MAX;
 
-    // ── 4. Ejecutar Pulse con timeout ────────────────────────────────────
-    println!("Corriendo Algoritmo del Pulso (máximo 1 minutos)");
-    let (tx, rx) = mpsc::channel();
-    let graph_clone = Arc::clone(&graph);
-    thread::spawn(move || {
-        let result = pulse_algorithm::pulse_algorithm(&*graph_clone, s, e, resource_limit);
-        let _ = tx.send(result);
-    });
+    // ── 4. Ejecutar Pulse con presupuesto de tiempo ──────────────────────
+    if threads > 1 {
+        println!("Corriendo Algoritmo del Pulso con {} hilos (máximo {} segundos)", threads, time_limit);
+    } else {
+        println!("Corriendo Algoritmo del Pulso (máximo {} segundos)", time_limit);
+    }
+    let start = Instant::now();
+    let options = pulse_options();
+    let result = if threads > 1 {
+        parallel_pulse::parallel_pulse(&graph, s, e, resource_limit, threads, &options)
+    } else {
+        pulse_algorithm::pulse_algorithm(&graph, s, e, resource_limit, &options)
+    };
+    if let Some(cost) = report_pulse(&result, cost_bound, start) {
+        pulse_cost = cost;
+    }
 
-    match rx.recv_timeout(Duration::from_secs(60)) {
-        Ok(Some(best)) => {
-            println!(
-                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+    println!();
+
+    // ── 4b. Pulso bidireccional, si se pidió ─────────────────────────────
+    if bidirectional {
+        println!("Corriendo Algoritmo del Pulso bidireccional (máximo {} segundos)", time_limit);
+        let start = Instant::now();
+        let options = pulse_options();
+        let result = bidirectional_pulse::bidirectional_pulse(&graph, s, e, resource_limit, &options);
+        // si el pulso unidireccional no terminó, este es el óptimo de referencia
+        if let Some(cost) = report_pulse(&result, cost_bound, start) {
+            if pulse_cost == f64::MAX {
+                pulse_cost = cost;
+            }
+        }
+
+        println!();
+    }
+
+    // ── 4c. Pulso multi-recurso, si se dieron varios límites ─────────────
+    if let Some(limits) = &resource_limits {
+        println!("Corriendo Algoritmo del Pulso multi-recurso (máximo {} segundos)", time_limit);
+        let start = Instant::now();
+        let options = pulse_options();
+        let result = multi_resource_pulse::multi_resource_pulse(&multi_graph, s, e, limits, &options);
+        match &result.best {
+            Some(best) => println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo por recurso: {:?}",
                 best.path, best.cost, best.consumption
+            ),
+            None => println!("No existe un camino factible con los límites de recursos dados."),
+        }
+        println!(
+            "Duración: {:?}\nÓptimo probado: {}",
+            start.elapsed(),
+            if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+        );
+        report_stats(&result.stats);
+        println!();
+    }
+
+    // ── 4d. Pulso con ventanas de tiempo, si se dieron ───────────────────
+    if windows_file.is_some() {
+        println!("Corriendo Algoritmo del Pulso con ventanas de tiempo (máximo {} segundos)", time_limit);
+        let start = Instant::now();
+        let options = pulse_options();
+        let result = time_window_pulse::time_window_pulse(&graph, s, e, resource_limit, &windows, &options);
+        match &result.best {
+            Some(best) => println!(
+                "Mejor camino: {:?}\nCosto total: {}\nInicio de servicio por nodo: {:?}",
+                best.path, best.cost, best.times
+            ),
+            None => println!("No existe un camino factible con las ventanas de tiempo dadas."),
+        }
+        println!(
+            "Duración: {:?}\nÓptimo probado: {}",
+            start.elapsed(),
+            if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+        );
+        report_stats(&result.stats);
+        println!();
+    }
+
+    // ── 4e. Pricing con costos reducidos, si se dieron duales ────────────
+    if let Some(duals_file) = &duals_file {
+        // nodos sin dual: 0
+        let mut duals: Vec<i64> = vec![0; graph.len()];
+        for line in io::BufReader::new(File::open(duals_file)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 2 {
+                eprintln!("Dual inválido: se esperaba `nodo dual` en la línea `{}`", line);
+                std::process::exit(1);
+            }
+            let v: usize = parts[0].parse().expect("Índice de nodo inválido");
+            if v >= duals.len() {
+                eprintln!("El nodo {} de los duales está fuera de rango (0..{}).", v, duals.len() - 1);
+                std::process::exit(1);
+            }
+            duals[v] = parts[1].parse().expect("Dual inválido");
+        }
+
+        let Some(reduced_graph) = pricing_pulse::reduced_cost_graph(&graph, &duals) else {
+            eprintln!("El pricing necesita costos enteros y el grafo tiene costos con decimales.");
+            std::process::exit(1);
+        };
+        println!("Corriendo pricing ESPPRC con costos reducidos (máximo {} segundos)", time_limit);
+        let start = Instant::now();
+        let result = pricing_pulse::pricing_pulse(&reduced_graph, s, e, resource_limit, max_columns, &pulse_options().budget);
+        if result.columns.is_empty() {
+            println!("No existe un camino factible de costo reducido negativo.");
+        }
+        if result.negative_cycle {
+            println!("Hay ciclos de costo reducido negativo: en los nodos que los alcanzan se poda con una cota más débil.");
+        }
+        for column in &result.columns {
+            println!(
+                "Columna: {:?}\nCosto reducido: {}\nConsumo total: {}",
+                column.path, column.reduced_cost, column.consumption
             );
-            pulse_cost = best.cost as f64;
         }
-        Ok(None) => println!("No existe un camino factible con el límite de recursos dado."),
-        Err(mpsc::RecvTimeoutError::Timeout) =>
-            println!("Timeout: Pulse superó los 3 minutos. Pasando al siguiente algoritmo."),
-        Err(e) => println!("Error recibiendo resultado de Pulse: {:?}", e),
+        println!(
+            "Duración: {:?}\nÓptimo probado: {}",
+            start.elapsed(),
+            if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+        );
+        println!();
     }
 
-    println!();
+    // ── 4f. Frente de Pareto completo, si se pidió ───────────────────────
+    if let Some(front_limit) = front_limit {
+        println!("Corriendo Algoritmo del Pulso para el frente de Pareto hasta {} (máximo {} segundos)", front_limit, time_limit);
+        let start = Instant::now();
+        let front = pareto_pulse::pareto_pulse(&graph, s, e, front_limit, &pulse_options());
+        if front.paths.is_empty() {
+            println!("No existe un camino factible con el límite de recursos dado.");
+        }
+        for path in &front.paths {
+            println!("Costo: {}, Consumo: {}, Camino: {:?}", path.cost, path.consumption, path.path);
+        }
+        println!(
+            "Puntos del frente: {}\nDuración: {:?}\nÓptimo probado: {}",
+            front.paths.len(),
+            start.elapsed(),
+            if front.status == PulseStatus::Optimal {"sí"} else {"no"}
+        );
+        report_stats(&front.stats);
+        println!();
+    }
+
+    // ── 4g. Búsqueda uno a todos desde el origen, si se pidió ────────────
+    if let Some(one_to_all_file) = &one_to_all_file {
+        println!("Corriendo búsqueda uno a todos desde {} (máximo {} segundos)", s, time_limit);
+        let start = Instant::now();
+        let result = one_to_all::one_to_all(&graph, s, resource_limit, &pulse_options().budget);
+        match (result.path(e), result.cost(e)) {
+            (Some(path), Some((cost, consumption))) => println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+                path, cost, consumption
+            ),
+            _ => println!("No existe un camino factible con el límite de recursos dado."),
+        }
+        let mut out = BufWriter::new(File::create(one_to_all_file)?);
+        for v in 0..graph.len() {
+            if let (Some(path), Some((cost, consumption))) = (result.path(v), result.cost(v)) {
+                let path: Vec<String> = path.iter().map(|u| u.to_string()).collect();
+                writeln!(out, "{} {} {} {}", v, cost, consumption, path.join(" "))?;
+            }
+        }
+        out.flush()?;
+        println!(
+            "Nodos alcanzables: {}\nDuración: {:?}\nÓptimo probado: {}",
+            result.reachable(),
+            start.elapsed(),
+            if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+        );
+        println!();
+    }
 
     // ── 5. Resto de algoritmos ──────────────────────────────────────────
     println!("Corriendo Algoritmo de buscar en la frontera de pareto");
     let start = Instant::now();
-    if let Some(best) = mult_obj_approach::mult_obj(&*graph, s, e, resource_limit, 0.1) {
+    let supported = mult_obj_approach::mult_obj(&graph, s, e, resource_limit);
+    for (path, cost, consumption) in &supported.hull {
+        println!("Costo: {}, Consumo: {}, Camino: {:?}", cost, consumption, path);
+    }
+    println!("Puntos soportados: {}", supported.hull.len());
+    if let Some(best) = &supported.best {
         println!(
             "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
             best.0, best.1, best.2
         );
-        curr_cost = best.1 as f64;
+        curr_cost = best.1.to_f64();
     } else {
         println!("No existe un camino factible con el límite de recursos dado.");
     }
     println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
     println!();
+    report_local_search(&graph, "la frontera de pareto", supported.best.as_ref(), resource_limit, local_search_time, pulse_cost);
+
+    println!("Corriendo LARAC");
+    let start = Instant::now();
+    if let Some(best) = larac::larac(&graph, s, e, resource_limit) {
+        println!(
+            "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}\nMultiplicador: {}\nCota inferior: {}\nBrecha: {:.4}%",
+            best.path, best.cost, best.consumption, best.lambda, best.lower_bound, 100.0 * best.gap()
+        );
+        curr_cost = best.cost.to_f64();
+    } else {
+        println!("No existe un camino factible con el límite de recursos dado.");
+    }
+    println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
+    println!();
+
+    if let Some(epsilon) = epsilon {
+        println!("Corriendo FPTAS con ε = {} (máximo {} segundos)", epsilon, time_limit);
+        let start = Instant::now();
+        if let Some(best) = fptas::fptas(&graph, s, e, resource_limit, epsilon, &pulse_options().budget) {
+            println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}\nCota inferior: {}",
+                best.path, best.cost, best.consumption, best.lower_bound
+            );
+            if best.status == PulseStatus::Interrupted {
+                println!("Timeout: se devuelve el mejor camino encontrado, sin la garantía (1 + ε).");
+            }
+            curr_cost = best.cost.to_f64();
+        } else {
+            println!("No existe un camino factible con el límite de recursos dado.");
+        }
+        println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
+        println!();
+    }
+
+    if let Some(k) = yen_paths {
+        println!("Corriendo Yen con k = {} (máximo {} segundos)", k, time_limit);
+        let start = Instant::now();
+        let result = yen::yen(&graph, s, e, resource_limit, k, &pulse_options().budget);
+        for (i, path) in result.paths.iter().enumerate() {
+            println!("Camino {}: Costo: {}, Consumo: {}, {:?}", i + 1, path.cost, path.consumption, path.path);
+        }
+        if let Some(best) = &result.best {
+            println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+                best.path, best.cost, best.consumption
+            );
+            curr_cost = best.cost.to_f64();
+        } else if result.status == PulseStatus::Optimal {
+            println!("No existe un camino factible con el límite de recursos dado.");
+        } else {
+            println!("Ninguno de los {} caminos más baratos examinados es factible.", result.paths.len());
+        }
+        println!(
+            "Caminos examinados: {}\nDuración: {:?}\nApproximation: {}",
+            result.paths.len(),
+            start.elapsed(),
+            curr_cost / pulse_cost
+        );
+        println!();
+    }
 
     println!("Corriendo Algoritmo de los caminos disyuntos");
     let start = Instant::now();
-    if let Some(best) = disjoint_path_approach::disjoint_algo(&*graph, s, e, resource_limit) {
+    let result = disjoint_path_approach::disjoint_algo(&graph, s, e, resource_limit);
+    if let Some(best) = &result {
         println!(
             "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
             best.0, best.1, best.2
         );
-        curr_cost = best.1 as f64;
+        curr_cost = best.1.to_f64();
     } else {
         println!("No existe un camino factible con el límite de recursos dado.");
     }
     println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
     println!();
+    report_local_search(&graph, "los caminos disyuntos", result.as_ref(), resource_limit, local_search_time, pulse_cost);
 
     println!("Corriendo edge block");
     let start = Instant::now();
-    if let Some(best) = edge_blocking_algo::edge_block(&*graph, s, e, resource_limit) {
+    let result = edge_blocking_algo::edge_block(&graph, s, e, resource_limit);
+    if let Some(best) = &result {
         println!(
             "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
             best.0, best.1, best.2
         );
-        curr_cost = best.1 as f64;
+        curr_cost = best.1.to_f64();
     } else {
         println!("No existe un camino factible con el límite de recursos dado.");
     }
     println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
     println!();
+    report_local_search(&graph, "edge block", result.as_ref(), resource_limit, local_search_time, pulse_cost);
 
     println!("Corriendo edge penalization");
     let start = Instant::now();
-    if let Some(best) = edge_penalization::edge_penalization(&*graph, s, e, resource_limit) {
+    let result = edge_penalization::edge_penalization(&graph, s, e, resource_limit);
+    if let Some(best) = &result {
         println!(
             "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
             best.0, best.1, best.2
         );
-        curr_cost = best.1 as f64;
+        curr_cost = best.1.to_f64();
     } else {
         println!("No existe un camino factible con el límite de recursos dado.");
     }
     println!("Duración: {:?}\nApproximation: {}", start.elapsed(), curr_cost / pulse_cost);
     println!();
+    report_local_search(&graph, "edge penalization", result.as_ref(), resource_limit, local_search_time, pulse_cost);
+
+    if let Some(params) = &genetic_params {
+        let penalty = params.penalty.map_or(String::new(), |penalty| format!(", penalización {}", penalty));
+        println!(
+            "Corriendo Algoritmo genético (codificación {}, población {}, {} generaciones, cruce {}, mutación {}{}, máximo {} segundos)",
+            params.encoding, params.population_size, params.generations, params.crossover_rate, params.mutation_rate, penalty, time_limit
+        );
+        let start = Instant::now();
+        // sin camino no hay aproximación: no se arrastra la del algoritmo anterior
+        curr_cost = f64::MAX;
+        let result = genetic_rcsp::genetic_algorithm(&graph, s, e, resource_limit, params, seed, &pulse_options().budget);
+        if let Some(best) = &result.best {
+            println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+                best.0, best.1, best.2
+            );
+            curr_cost = best.1.to_f64();
+        } else {
+            println!("No existe un camino factible con el límite de recursos dado.");
+        }
+        if result.interrupted {
+            println!("Timeout: se devuelve el mejor camino encontrado antes del límite de tiempo.");
+        }
+        println!(
+            "Generaciones completas: {}\nDuración: {:?}\nApproximation: {}",
+            result.generations,
+            start.elapsed(),
+            curr_cost / pulse_cost
+        );
+        println!();
+    }
+
+    if let Some(params) = &ant_params {
+        println!(
+            "Corriendo Colonia de hormigas ({} hormigas, {} iteraciones, evaporación {}, máximo {} segundos)",
+            params.colony_size, params.iterations, params.evaporation, time_limit
+        );
+        let start = Instant::now();
+        curr_cost = f64::MAX;
+        let result = ant_colony::ant_colony(&graph, s, e, resource_limit, params, seed, &pulse_options().budget);
+        if let Some(best) = &result.best {
+            println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+                best.0, best.1, best.2
+            );
+            curr_cost = best.1.to_f64();
+        } else {
+            println!("Ninguna hormiga encontró un camino factible.");
+        }
+        if result.interrupted {
+            println!("Timeout: se devuelve el mejor camino encontrado antes del límite de tiempo.");
+        }
+        println!(
+            "Iteraciones completas: {}\nDuración: {:?}\nApproximation: {}",
+            result.iterations,
+            start.elapsed(),
+            curr_cost / pulse_cost
+        );
+        println!();
+    }
+
+    if let Some(params) = &tabu_params {
+        println!(
+            "Corriendo Búsqueda tabú ({} iteraciones, permanencia {}, máximo {} segundos)",
+            params.iterations, params.tenure, time_limit
+        );
+        let start = Instant::now();
+        curr_cost = f64::MAX;
+        let result = tabu_search::tabu_search(&graph, s, e, resource_limit, params, &pulse_options().budget);
+        if let Some(best) = &result.best {
+            println!(
+                "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+                best.0, best.1, best.2
+            );
+            curr_cost = best.1.to_f64();
+        } else {
+            println!("No existe un camino factible con el límite de recursos dado.");
+        }
+        if result.interrupted {
+            println!("Timeout: se devuelve el mejor camino encontrado antes del límite de tiempo.");
+        }
+        println!(
+            "Movimientos: {}\nDuración: {:?}\nApproximation: {}",
+            result.iterations,
+            start.elapsed(),
+            curr_cost / pulse_cost
+        );
+        println!();
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// El mejor camino factible de las heurísticas rápidas, para arrancar los pulsos.
+fn heuristic_incumbent<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W) -> Option<(Vec<usize>, W, W)> {
+    [
+        mult_obj_approach::mult_obj(graph, s, e, resource_limit).best,
+        edge_blocking_algo::edge_block(graph, s, e, resource_limit),
+        disjoint_path_approach::disjoint_algo(graph, s, e, resource_limit),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|(_, _, consumption)| *consumption <= resource_limit)
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Si se pidió `--busqueda-local`, mejora el camino de una heurística e imprime el resultado.
+fn report_local_search<W: Weight>(graph: &[Vec<(usize, W, W)>], name: &str, path: Option<&(Vec<usize>, W, W)>, resource_limit: W, seconds: Option<u64>, pulse_cost: f64) {
+    let (Some(seconds), Some(path)) = (seconds, path) else { return };
+    println!("Corriendo búsqueda local sobre {} (máximo {} segundos)", name, seconds);
+    let start = Instant::now();
+    let budget = if seconds == 0 {Budget::unlimited()} else {Budget::timeout(Duration::from_secs(seconds))};
+    let result = local_search::local_search(graph, path, resource_limit, &budget);
+    let (best, cost, consumption) = &result.best;
+    println!(
+        "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}\nMovimientos de mejora: {}\nÓptimo local: {}",
+        best, cost, consumption, result.moves,
+        if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+    );
+    println!("Duración: {:?}\nApproximation: {}", start.elapsed(), cost.to_f64() / pulse_cost);
+    println!();
+}
+
+/// Imprime el resultado de un algoritmo del pulso y devuelve su costo si se probó óptimo.
+/// `cost_bound` es la cota de `--inicio cota:<costo>`, si se dio.
+fn report_pulse<W: Weight>(result: &PulseResult<Pulse<W>>, cost_bound: Option<W>, start: Instant) -> Option<f64> {
+    match (&result.best, cost_bound) {
+        (Some(best), _) => println!(
+            "Mejor camino: {:?}\nCosto total: {}\nConsumo total: {}",
+            best.path, best.cost, best.consumption
+        ),
+        (None, Some(bound)) => println!("No existe un camino factible más barato que la cota inicial {}.", bound),
+        (None, None) => println!("No existe un camino factible con el límite de recursos dado."),
+    }
+    if result.status == PulseStatus::Interrupted {
+        println!("Timeout: se devuelve el mejor camino encontrado antes del límite de tiempo.");
+    }
+    println!(
+        "Duración: {:?}\nÓptimo probado: {}",
+        start.elapsed(),
+        if result.status == PulseStatus::Optimal {"sí"} else {"no"}
+    );
+    report_stats(&result.stats);
+
+    match (&result.best, result.status) {
+        (Some(best), PulseStatus::Optimal) => Some(best.cost.to_f64()),
+        _ => None,
+    }
+}
+
+/// Imprime las estadísticas de búsqueda de un algoritmo del pulso.
+fn report_stats(stats: &PulseStats) {
+    println!(
+        "Pulsos expandidos: {}\nPodas por dominancia: {}\nPodas por cota: {}\nPodas por cota lagrangiana: {}\n\
+         Podas por infactibilidad: {}\nProfundidad máxima: {}\nTiempo en cotas: {:?}\nTiempo en la cota lagrangiana: {:?}",
+        stats.expanded,
+        stats.pruned_by_dominance,
+        stats.pruned_by_bound,
+        stats.pruned_by_lagrangian,
+        stats.pruned_by_infeasibility,
+        stats.max_depth,
+        stats.bounds_time,
+        stats.lagrangian_time
+    );
+    println!("Mejoras del incumbente: {}", stats.incumbents.len());
+    for (time, cost) in &stats.incumbents {
+        println!("  {:?}: {}", time, cost);
+    }
+}
+
+/// Interpreta `value` como un `T`, o corta con `message`.
+fn parse<T: FromStr>(value: &str, message: &str) -> T {
+    value.parse().unwrap_or_else(|_| panic!("{}: {}", message, value))
+}
+
+/// Si la opción `--nombre` (sin valor) está en la línea de comandos.
+fn flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|arg| arg == name)
+}
+
+/// Valor de la opción `--nombre valor` en la línea de comandos, si está presente.
+fn option<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    let pos = args.iter().position(|arg| arg == name)?;
+    let value = args.get(pos + 1).unwrap_or_else(|| panic!("Falta el valor de {}", name));
+    Some(value.parse().unwrap_or_else(|_| panic!("Valor inválido para {}: {}", name, value)))
+}
@@ -1,70 +1,95 @@
 use std::{cmp::Reverse, collections::BinaryHeap};
 
-/// Implementación del enfoque multi-objetivo para RCSPP
-/// Utiliza una ponderación entre costo y recurso que se va ajustando
+use crate::weight::{Ordered, Weight};
 
-use std::cmp::Ordering;
+// entrada del heap de Dijkstra: (costo combinado, nodo, costo real, recurso real); el
+// costo combinado es siempre `f64` y `Ordered` le da el orden total (total_cmp)
+type Entry<W> = Reverse<(Ordered<f64>, usize, Ordered<W>, Ordered<W>)>;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-struct F64(f64);
+// holgura relativa (en las coordenadas normalizadas) para aceptar un punto nuevo del casco
+const HULL_TOLERANCE: f64 = 1e-9;
 
-impl Eq for F64 {}
-
-impl PartialOrd for F64 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // Usamos total_cmp para definir un orden total
-        Some(self.0.total_cmp(&other.0))
-    }
-}
-
-impl Ord for F64 {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.0.total_cmp(&other.0)
-    }
+/// Puntos soportados del frente (costo, consumo) y el mejor camino factible entre ellos.
+#[derive(Debug, Clone)]
+pub struct SupportedHull<W> {
+    /// vértices del casco convexo inferior, por costo creciente (y consumo decreciente)
+    pub hull: Vec<(Vec<usize>, W, W)>,
+    pub best: Option<(Vec<usize>, W, W)>,
 }
 
-pub fn mult_obj(graph: &Vec<Vec<(usize, u32, u32)>>, s: usize, e: usize, resource_limit: u32, inc: f64) -> Option<(Vec<usize>, u32, u32)> {
-    let mut lambda = 0.0;
-    let mut best_path = None;
-    let mut best_cost = u32::MAX;
-    let mut best_resource = u32::MAX;
-
-    // Vamos incrementando lambda desde 0 hasta 1
-    while lambda <= 1.0 {
-        // Definimos una función de costo que combina costo y recurso usando lambda
-        let fn_cost = move |cost: u32, resource: u32| -> f64 {
-            lambda * (cost as f64) + (1.0 - lambda) * (resource as f64)
-        };
-
-        // Ejecutamos Dijkstra con la función de costo actual
-        if let Some((path, total_cost, total_resource)) = dijkstra_with_tracking(graph, s, e, fn_cost) {
-            // Verificamos si el camino es factible y mejor que el actual
-            if total_resource <= resource_limit && (total_cost < best_cost || (total_cost == best_cost && total_resource < best_resource)) {
-                best_path = Some(path);
-                best_cost = total_cost;
-                best_resource = total_resource;
+// Implementación del enfoque multi-objetivo para RCSPP
+// Búsqueda dicotómica de Aneja–Nair: entre cada par de puntos vecinos del casco se busca
+// con los pesos perpendiculares al segmento que los une, hasta que no aparece otro punto
+
+pub fn mult_obj<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W) -> SupportedHull<W> {
+    let empty = SupportedHull {hull: Vec::new(), best: None};
+    // extremos: el camino de mínimo costo y el de mínimo recurso
+    let Some(cheapest) = dijkstra_with_tracking(graph, s, e, |cost: W, _resource: W| cost.to_f64()) else { return empty };
+    let Some(lightest) = dijkstra_with_tracking(graph, s, e, |_cost: W, resource: W| resource.to_f64()) else { return empty };
+
+    let mut hull = vec![cheapest.clone()];
+    if cheapest.1 < lightest.1 && cheapest.2 > lightest.2 {
+        // se normaliza por el rango de cada objetivo, así la escala no sesga λ
+        let cost_range = lightest.1.to_f64() - cheapest.1.to_f64();
+        let resource_range = cheapest.2.to_f64() - lightest.2.to_f64();
+        let normalize = move |cost: W, resource: W| (cost.to_f64() / cost_range, resource.to_f64() / resource_range);
+
+        // pares de puntos vecinos del casco que falta separar
+        let mut pending = vec![(cheapest, lightest.clone())];
+        while let Some((left, right)) = pending.pop() {
+            let (left_cost, left_resource) = normalize(left.1, left.2);
+            let (right_cost, right_resource) = normalize(right.1, right.2);
+            // λ tal que `left` y `right` valen lo mismo en `λ·costo + (1 - λ)·recurso`
+            let lambda = (left_resource - right_resource) / ((left_resource - right_resource) + (right_cost - left_cost));
+            let combined = move |cost: W, resource: W| {
+                let (cost, resource) = normalize(cost, resource);
+                lambda * cost + (1.0 - lambda) * resource
+            };
+
+            let Some(path) = dijkstra_with_tracking(graph, s, e, combined) else { continue };
+            let value = combined(path.1, path.2);
+            let line = combined(left.1, left.2);
+            if value < line - HULL_TOLERANCE * line.abs().max(1.0) {
+                hull.push(path.clone());
+                pending.push((left, path.clone()));
+                pending.push((path, right));
             }
         }
-
-        lambda += inc;
+        hull.push(lightest);
+    } else if lightest.1 <= cheapest.1 {
+        // el de mínimo recurso cuesta lo mismo y consume menos: es el único punto
+        hull[0] = lightest;
     }
 
-    // Si encontramos un camino factible, lo devolvemos junto con su costo y consumo
-    best_path.map(|path| (path, best_cost, best_resource))
+    // con empates en Dijkstra un extremo puede quedar dominado por otro punto de igual costo
+    hull.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)));
+    let mut min_resource = W::MAX;
+    hull.retain(|(_, _, resource)| {
+        let keep = *resource < min_resource;
+        if keep {
+            min_resource = *resource;
+        }
+        keep
+    });
+    let best = hull.iter()
+        .filter(|(_, _, resource)| *resource <= resource_limit)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .cloned();
+    SupportedHull {hull, best}
 }
 
 /// Versión mejorada de Dijkstra que devuelve el camino, costo total y recurso total
-fn dijkstra_with_tracking(
-    graph: &Vec<Vec<(usize, u32, u32)>>, 
+pub(crate) fn dijkstra_with_tracking<W: Weight>(
+    graph: &[Vec<(usize, W, W)>], 
     s: usize, 
     e: usize, 
-    fn_cost: impl Fn(u32, u32) -> f64
-) -> Option<(Vec<usize>, u32, u32)> {
+    fn_cost: impl Fn(W, W) -> f64
+) -> Option<(Vec<usize>, W, W)> {
     // Cola de prioridad para Dijkstra
-    let mut heap: BinaryHeap<Reverse<(F64, usize, u32, u32)>> = BinaryHeap::new();
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
     
     // Inicializamos con el nodo origen (costo combinado, nodo, costo real, recurso real)
-    heap.push(Reverse((F64(0.0), s, 0, 0)));
+    heap.push(Reverse((Ordered(0.0), s, Ordered(W::ZERO), Ordered(W::ZERO))));
     
     // Vector de distancias (valor combinado)
     let mut dist = vec![f64::MAX; graph.len()];
@@ -74,14 +99,14 @@ fn dijkstra_with_tracking(
     let mut parent = vec![None; graph.len()];
     
     // Almacenamos el costo y recurso reales para cada nodo
-    let mut real_cost = vec![u32::MAX; graph.len()];
-    let mut real_resource = vec![u32::MAX; graph.len()];
-    real_cost[s] = 0;
-    real_resource[s] = 0;
+    let mut real_cost = vec![W::MAX; graph.len()];
+    let mut real_resource = vec![W::MAX; graph.len()];
+    real_cost[s] = W::ZERO;
+    real_resource[s] = W::ZERO;
 
-    while let Some(Reverse((f_cost, node, cost, resource))) = heap.pop() {
+    while let Some(Reverse((f_cost, node, Ordered(cost), Ordered(resource)))) = heap.pop() {
         // Si ya encontramos un mejor camino a este nodo, ignoramos
-        if f_cost > F64(dist[node]) {
+        if f_cost > Ordered(dist[node]) {
             continue;
         }
 
@@ -93,8 +118,10 @@ fn dijkstra_with_tracking(
 
         // Exploramos los vecinos
         for &(next_node, edge_cost, edge_resource) in &graph[node] {
-            let next_cost = cost + edge_cost;
-            let next_resource = resource + edge_resource;
+            // un camino cuya suma desborda no se sigue
+            let (Some(next_cost), Some(next_resource)) = (cost.checked_add(edge_cost), resource.checked_add(edge_resource)) else {
+                continue;
+            };
             
             // Calculamos el costo combinado
             let next_f_cost = fn_cost(next_cost, next_resource);
@@ -106,7 +133,7 @@ fn dijkstra_with_tracking(
                 real_resource[next_node] = next_resource;
                 parent[next_node] = Some(node);
                 
-                heap.push(Reverse((F64(next_f_cost), next_node, next_cost, next_resource)));
+                heap.push(Reverse((Ordered(next_f_cost), next_node, Ordered(next_cost), Ordered(next_resource))));
             }
         }
     }
@@ -116,7 +143,7 @@ fn dijkstra_with_tracking(
 }
 
 /// Reconstruye el camino desde el origen hasta el destino
-fn get_path(parent: &Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>> {
+fn get_path(parent: &[Option<usize>], mut curr: usize) -> Option<Vec<usize>> {
     let mut path = Vec::new();
     path.push(curr); 
     
@@ -129,3 +156,85 @@ fn get_path(parent: &Vec<Option<usize>>, mut curr: usize) -> Option<Vec<usize>>
     Some(path)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{all_paths, from_edges, is_feasible, limits, optimum, random_graph};
+
+    // vértices del casco convexo inferior de los puntos (costo, consumo), por costo creciente
+    fn lower_hull(points: &[(i64, i64)]) -> Vec<(i64, i64)> {
+        let mut sorted = points.to_vec();
+        sorted.sort();
+        let mut hull: Vec<(i64, i64)> = Vec::new();
+        for (cost, consumption) in sorted {
+            if hull.last().is_some_and(|&(_, last)| consumption >= last) {
+                continue;
+            }
+            // se quitan los vértices que no quedan estrictamente por debajo de la cuerda
+            while let [.., (c1, r1), (c2, r2)] = hull[..] {
+                if (c2 - c1) * (consumption - r1) - (r2 - r1) * (cost - c1) > 0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push((cost, consumption));
+        }
+        hull
+    }
+
+    #[test]
+    fn skips_points_above_the_hull() {
+        // (5, 7) no está dominado, pero queda sobre la cuerda de (4, 8) a (6, 4)
+        let graph = from_edges(6, &[
+            (0, 5, 1, 20),
+            (0, 1, 2, 4), (1, 5, 2, 4),
+            (0, 2, 3, 2), (2, 5, 3, 2),
+            (0, 3, 2, 4), (3, 5, 3, 3),
+        ]);
+        let result = mult_obj(&graph, 0, 5, 7);
+        let points: Vec<(u32, u32)> = result.hull.iter().map(|&(_, cost, consumption)| (cost, consumption)).collect();
+        assert_eq!(points, vec![(1, 20), (4, 8), (6, 4)]);
+        // por eso con límite 7 devuelve (6, 4) y no el óptimo (5, 7)
+        assert_eq!(result.best.map(|(path, cost, _)| (path, cost)), Some((vec![0, 2, 5], 6)));
+        assert_eq!(mult_obj(&graph, 0, 5, 3).best, None);
+    }
+
+    #[test]
+    fn finds_the_supported_points() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            let points: Vec<(i64, i64)> = all_paths(&graph, 0, 7).into_iter().map(|(_, cost, consumption)| (cost as i64, consumption as i64)).collect();
+            let vertices = lower_hull(&points);
+            // un punto soportado minimiza alguna combinación de los objetivos: un eje o la normal de una arista
+            let mut weights = vec![(1, 0), (0, 1)];
+            weights.extend(vertices.windows(2).map(|ab| (ab[0].1 - ab[1].1, ab[1].0 - ab[0].0)));
+            let minimum = |(a, b): (i64, i64)| points.iter().map(|&(cost, consumption)| a * cost + b * consumption).min().unwrap();
+
+            for limit in limits(&graph, 0, 7) {
+                let result = mult_obj(&graph, 0, 7, limit);
+                let found: Vec<(i64, i64)> = result.hull.iter().map(|&(_, cost, consumption)| (cost as i64, consumption as i64)).collect();
+                for (path, cost, consumption) in &result.hull {
+                    assert!(is_feasible(&graph, 0, 7, u32::MAX, path, *cost, *consumption));
+                }
+                for &(cost, consumption) in &found {
+                    assert!(weights.iter().any(|&(a, b)| a * cost + b * consumption == minimum((a, b))), "semilla {}: ({}, {}) no es soportado", seed, cost, consumption);
+                }
+                assert!(found.windows(2).all(|ab| ab[0].0 < ab[1].0 && ab[0].1 > ab[1].1));
+                // los extremos pueden empatar en un objetivo, los vértices interiores salen tal cual
+                assert_eq!(found[0].0, vertices[0].0);
+                assert_eq!(found.last().unwrap().1, vertices.last().unwrap().1);
+                if vertices.len() > 2 {
+                    assert!(vertices[1..vertices.len() - 1].iter().all(|vertex| found.contains(vertex)), "semilla {}: {:?} vs {:?}", seed, found, vertices);
+                }
+
+                if let Some((path, cost, consumption)) = &result.best {
+                    assert!(is_feasible(&graph, 0, 7, limit, path, *cost, *consumption));
+                    assert!(optimum(&graph, 0, 7, limit) <= Some(*cost));
+                } else {
+                    assert_eq!(optimum(&graph, 0, 7, limit), None);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::labels::{distance, LabelStrategy};
+use std::time::Instant;
+
+use crate::pulse_algorithm::{depth_first, get_bounds, PartialPath, Prune, PulseOptions, PulseResult, PulseStats, Step};
+use crate::weight::Weight;
+
+/// Arco con varios recursos: (nodo, costo, consumo de cada recurso)
+pub type MultiEdge<W> = (usize, W, Vec<W>);
+
+/// Pulso con un vector de consumos, uno por recurso.
+#[derive(Debug, Clone)]
+pub struct MultiPulse<W> {
+    pub path: Vec<usize>,
+    pub cost: W,
+    pub consumption: Vec<W>,
+    pub last_node: usize,
+    pub visited: Vec<bool>,
+    // (costo, consumos) antes de cada arco agregado, como en `Pulse`
+    trail: Vec<MultiLabel<W>>,
+}
+
+impl<W: Weight> MultiPulse<W> {
+    fn start(s: usize, num_nodes: usize, num_resources: usize) -> Self {
+        let mut visited = vec![false; num_nodes];
+        visited[s] = true;
+        MultiPulse {path: vec![s], cost: W::ZERO, consumption: vec![W::ZERO; num_resources], last_node: s, visited, trail: Vec::new()}
+    }
+
+    fn add_edge(&mut self, edge: &MultiEdge<W>) {
+        self.trail.push((self.cost, self.consumption.clone()));
+        self.path.push(edge.0);
+        self.visited[edge.0] = true;
+        self.last_node = edge.0;
+        // un desborde queda en `W::MAX`, que las reglas de poda descartan
+        self.cost = self.cost.checked_add(edge.1).unwrap_or(W::MAX);
+        self.consumption.iter_mut().zip(&edge.2).for_each(|(total, &r)| *total = total.checked_add(r).unwrap_or(W::MAX));
+    }
+
+    fn remove_edge(&mut self, edge: &MultiEdge<W>) {
+        self.path.pop();
+        self.visited[edge.0] = false;
+        self.last_node = *self.path.last().unwrap();
+        (self.cost, self.consumption) = self.trail.pop().expect("se quitó un arco que no se había agregado");
+    }
+
+    // reglas de poda
+    fn check_bounds(&self, primal_bound: W, minimum_cost: &[W]) -> bool {
+        minimum_cost[self.last_node] < primal_bound && self.cost.add_fits(minimum_cost[self.last_node], primal_bound)
+    }
+
+    // factible solo si cada recurso, con su cota hacia `e`, cabe en su límite
+    fn check_feasibility(&self, resource_limits: &[W], minimum_consumption: &[Vec<W>]) -> bool {
+        resource_limits.iter().zip(minimum_consumption).zip(&self.consumption)
+            .all(|((&limit, minimum), &consumption)| {
+                minimum[self.last_node] <= limit && consumption.add_fits(minimum[self.last_node], limit)
+            })
+    }
+}
+
+impl<W: Weight> PartialPath<MultiEdge<W>> for MultiPulse<W> {
+    fn last_node(&self) -> usize {
+        self.last_node
+    }
+
+    fn closes_cycle(&self, edge: &MultiEdge<W>) -> bool {
+        self.visited[edge.0]
+    }
+
+    fn push_edge(&mut self, edge: &MultiEdge<W>) -> bool {
+        self.add_edge(edge);
+        true
+    }
+
+    fn pop_edge(&mut self, edge: &MultiEdge<W>) {
+        self.remove_edge(edge);
+    }
+}
+
+// (costo, consumos) de un pulso guardado en un nodo
+type MultiLabel<W> = (W, Vec<W>);
+
+fn dominates<W: Weight>(label: &MultiLabel<W>, pulse: &MultiPulse<W>) -> bool {
+    label.0 <= pulse.cost && label.1.iter().zip(&pulse.consumption).all(|(r, consumption)| r <= consumption)
+}
+
+/// Labels por nodo en varias dimensiones, con las mismas dos estrategias que el pulso de un recurso.
+enum MultiLabels<W> {
+    /// mejor costo, mejor consumo de cada recurso y uno al azar; poda solo si todos dominan
+    Slots { labels: Vec<Vec<Option<MultiLabel<W>>>>, rng: Box<StdRng> },
+    /// conjunto de Pareto acotado, ordenado por costo creciente; poda si alguno domina.
+    /// Lleno, descarta como `labels::ParetoLabels` el vecino más cercano en costo al label nuevo
+    Pareto { capacity: usize, labels: Vec<Vec<MultiLabel<W>>> },
+}
+
+impl<W: Weight> MultiLabels<W> {
+    fn new(strategy: LabelStrategy, num_nodes: usize, seed: u64) -> Self {
+        match strategy {
+            LabelStrategy::Three => MultiLabels::Slots {labels: vec![Vec::new(); num_nodes], rng: Box::new(StdRng::seed_from_u64(seed))},
+            LabelStrategy::Pareto { capacity } => MultiLabels::Pareto {capacity, labels: vec![Vec::new(); num_nodes]},
+        }
+    }
+
+    fn is_dominated(&self, pulse: &MultiPulse<W>) -> bool {
+        match self {
+            MultiLabels::Slots { labels, .. } => {
+                let slots = &labels[pulse.last_node];
+                // generaliza `is_dominated`: hace falta que todos los labels existan y dominen
+                pulse.consumption.len() + 2 == slots.iter().flatten().filter(|label| dominates(label, pulse)).count()
+            }
+            MultiLabels::Pareto { labels, .. } => labels[pulse.last_node].iter().any(|label| dominates(label, pulse)),
+        }
+    }
+
+    fn update(&mut self, pulse: &MultiPulse<W>) {
+        let label = || (pulse.cost, pulse.consumption.clone());
+        match self {
+            MultiLabels::Slots { labels, rng } => {
+                let slots = &mut labels[pulse.last_node];
+                if slots.is_empty() {
+                    *slots = vec![None; pulse.consumption.len() + 2];
+                }
+                let (first, rest) = slots.split_first_mut().unwrap();
+                let (third, by_resource) = rest.split_last_mut().unwrap();
+
+                if first.as_ref().is_none_or(|(cost, _)| pulse.cost < *cost) {
+                    *first = Some(label());
+                }
+                for (i, slot) in by_resource.iter_mut().enumerate() {
+                    if slot.as_ref().is_none_or(|(_, consumption)| pulse.consumption[i] < consumption[i]) {
+                        *slot = Some(label());
+                    }
+                }
+                if third.is_none() || rng.random_bool(0.5) {
+                    *third = Some(label());
+                }
+            }
+            MultiLabels::Pareto { capacity, labels } => {
+                let stored = &mut labels[pulse.last_node];
+                if stored.iter().any(|label| dominates(label, pulse)) {
+                    return;
+                }
+                stored.retain(|(cost, consumption)| {
+                    *cost < pulse.cost || consumption.iter().zip(&pulse.consumption).any(|(r, own)| r < own)
+                });
+                let pos = stored.partition_point(|(cost, _)| *cost < pulse.cost);
+                stored.insert(pos, label());
+                if stored.len() > *capacity {
+                    // se conservan los extremos: el más barato y el de menor consumo de cada recurso
+                    let extreme = |i: usize| {
+                        i == 0 || (0..pulse.consumption.len()).any(|k| stored.iter().all(|other| stored[i].1[k] <= other.1[k]))
+                    };
+                    let removed = [pos.checked_sub(1), Some(pos + 1)].into_iter()
+                        .flatten()
+                        .filter(|&i| i < stored.len() && !extreme(i))
+                        .min_by(|&i, &j| distance(stored[i].0, pulse.cost).total_cmp(&distance(stored[j].0, pulse.cost)))
+                        .unwrap_or(pos);
+                    stored.remove(removed);
+                }
+            }
+        }
+    }
+}
+
+/// Algoritmo del pulso con varios recursos.
+///
+/// Cada arco consume un vector de recursos y cada recurso tiene su propio límite en
+/// `resource_limits`. Las cotas de consumo mínimo hacia `e` se calculan con `get_bounds`
+/// una vez por recurso, y un pulso se poda si cualquiera de los recursos ya no cabe.
+pub fn multi_resource_pulse<W: Weight>(graph: &[Vec<MultiEdge<W>>], s: usize, e: usize, resource_limits: &[W], options: &PulseOptions<W>) -> PulseResult<MultiPulse<W>> {
+    let num_resources = resource_limits.len();
+    let mut stats = PulseStats::new();
+    let start = Instant::now();
+
+    // un grafo (nodo, costo, recurso k) por recurso para reutilizar `get_bounds`
+    let project = |k: usize| -> Vec<Vec<(usize, W, W)>> {
+        graph.iter()
+            .map(|adj| adj.iter().map(|(v, cost, resources)| (*v, *cost, resources[k])).collect())
+            .collect()
+    };
+    let minimum_consumption: Vec<Vec<W>> = (0..num_resources)
+        .map(|k| get_bounds(&project(k), e, |(_a, _b, c)| c))
+        .collect();
+    let minimum_cost = get_bounds(&project(0), e, |(_a, b, _c)| b);
+    stats.bounds_time = start.elapsed();
+
+    let mut labels = MultiLabels::new(options.labels, graph.len(), options.seed);
+    let mut curr = MultiPulse::start(s, graph.len(), num_resources);
+    let mut primal_bound = W::MAX;
+    let mut best_path = None;
+
+    labels.update(&curr);
+    let status = depth_first(graph, &options.budget, &mut curr, |curr| {
+        let prune = if labels.is_dominated(curr) {
+            Some(Prune::Dominance)
+        } else if !curr.check_bounds(primal_bound, &minimum_cost) {
+            Some(Prune::Bound)
+        } else if !curr.check_feasibility(resource_limits, &minimum_consumption) {
+            Some(Prune::Infeasibility)
+        } else {
+            None
+        };
+        if let Some(prune) = prune {
+            stats.count(prune);
+            return Step::Stop;
+        }
+        stats.max_depth = stats.max_depth.max(curr.path.len() - 1);
+
+        if curr.last_node == e {
+            if curr.cost < primal_bound {
+                primal_bound = curr.cost;
+                best_path = Some(curr.clone());
+                stats.record_incumbent(curr.cost);
+            }
+            return Step::Stop;
+        }
+        stats.expanded += 1;
+        labels.update(curr);
+        Step::Expand
+    });
+
+    PulseResult {best: best_path, status, stats}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pulse_algorithm::PulseStatus;
+    use crate::test_graphs::{all_paths, limits, random_graph};
+
+    // segundo recurso de prueba de cada arco, sin relación con el costo ni con el primero
+    fn second(u: usize, v: usize) -> u32 {
+        (u as u32 * 7 + v as u32 * 3) % 10 + 1
+    }
+
+    fn multi_graph(edges: &[(usize, usize, u32, [u32; 2])], n: usize) -> Vec<Vec<MultiEdge<u32>>> {
+        let mut graph = vec![Vec::new(); n];
+        for &(u, v, cost, resources) in edges {
+            graph[u].push((v, cost, resources.to_vec()));
+        }
+        graph
+    }
+
+    #[test]
+    fn the_second_resource_also_binds() {
+        // el arco directo cabe en el primer recurso pero no en el segundo
+        let graph = multi_graph(&[
+            (0, 3, 1, [1, 10]),
+            (0, 1, 2, [5, 1]), (1, 3, 2, [5, 1]),
+            (0, 2, 3, [1, 1]), (2, 3, 3, [1, 1]),
+        ], 4);
+        let path = |limits: [u32; 2]| multi_resource_pulse(&graph, 0, 3, &limits, &PulseOptions::default()).best.map(|best| best.path);
+        assert_eq!(path([20, 20]), Some(vec![0, 3]));
+        assert_eq!(path([20, 5]), Some(vec![0, 1, 3]));
+        assert_eq!(path([5, 5]), Some(vec![0, 2, 3]));
+        assert_eq!(path([1, 1]), None);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 8, 0.4);
+            let multi: Vec<Vec<MultiEdge<u32>>> = graph.iter().enumerate()
+                .map(|(u, adj)| adj.iter().map(|&(v, cost, consumption)| (v, cost, vec![consumption, second(u, v)])).collect())
+                .collect();
+            let paths: Vec<_> = all_paths(&graph, 0, 7).into_iter()
+                .map(|(path, cost, consumption)| {
+                    let other: u32 = path.windows(2).map(|uv| second(uv[0], uv[1])).sum();
+                    (path, cost, [consumption, other])
+                })
+                .collect();
+
+            for first in limits(&graph, 0, 7) {
+                for other in [5, 12, 25] {
+                    let limits = [first, other];
+                    let optimum = paths.iter()
+                        .filter(|(_, _, used)| used[0] <= first && used[1] <= other)
+                        .map(|(_, cost, _)| *cost)
+                        .min();
+                    for labels in [LabelStrategy::Three, LabelStrategy::Pareto { capacity: 2 }] {
+                        let result = multi_resource_pulse(&multi, 0, 7, &limits, &PulseOptions {labels, ..PulseOptions::default()});
+                        assert_eq!(result.status, PulseStatus::Optimal);
+                        if let Some(best) = &result.best {
+                            let found = paths.iter().find(|(path, _, _)| *path == best.path).unwrap();
+                            assert_eq!((found.1, found.2.to_vec()), (best.cost, best.consumption.clone()));
+                            assert!(best.consumption.iter().zip(&limits).all(|(used, limit)| used <= limit));
+                        }
+                        assert_eq!(result.best.map(|best| best.cost), optimum, "límites {:?}, semilla {}", limits, seed);
+                    }
+                }
+            }
+        }
+    }
+}
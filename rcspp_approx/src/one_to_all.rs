@@ -0,0 +1,125 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::pulse_algorithm::{Budget, PulseStatus, BUDGET_CHECK_INTERVAL};
+use crate::weight::{Ordered, Weight};
+
+// entrada del heap: (costo, consumo, nodo, label permanente del que viene)
+type Entry<W> = Reverse<(Ordered<W>, Ordered<W>, usize, usize)>;
+
+/// Caminos de costo mínimo con consumo a lo sumo el límite, desde un origen a todos los nodos.
+#[derive(Debug, Clone)]
+pub struct OneToAll<W> {
+    /// (nodo, label anterior) de cada label permanente; el del origen apunta a sí mismo
+    predecessors: Vec<(usize, usize)>,
+    /// (label permanente, costo, consumo) del mejor camino a cada nodo alcanzable
+    best: Vec<Option<(usize, W, W)>>,
+    pub status: PulseStatus,
+}
+
+impl<W: Weight> OneToAll<W> {
+    /// Costo y consumo del mejor camino factible a `v`, si existe (o si se alcanzó a encontrar).
+    pub fn cost(&self, v: usize) -> Option<(W, W)> {
+        self.best[v].map(|(_label, cost, consumption)| (cost, consumption))
+    }
+
+    /// Reconstruye el mejor camino factible a `v` siguiendo los predecesores.
+    pub fn path(&self, v: usize) -> Option<Vec<usize>> {
+        let (mut label, _cost, _consumption) = self.best[v]?;
+        let mut path = vec![v];
+        while self.predecessors[label].1 != label {
+            label = self.predecessors[label].1;
+            path.push(self.predecessors[label].0);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Cantidad de nodos con un camino factible desde el origen.
+    pub fn reachable(&self) -> usize {
+        self.best.iter().filter(|best| best.is_some()).count()
+    }
+}
+
+/// Búsqueda uno a todos por fijación de labels (label setting) desde `s`.
+///
+/// Sin destino no hay cotas como las del pulso, así que solo se poda por el límite de
+/// recurso. Los labels salen en orden lexicográfico (costo, consumo): el primero que sale
+/// en un nodo es su óptimo, y uno posterior solo sirve si consume menos que todos los
+/// anteriores de ese nodo. Con pesos no negativos los labels no dominados son caminos
+/// elementales. Si se acaba el presupuesto, los nodos ya alcanzados tienen su óptimo.
+pub fn one_to_all<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, resource_limit: W, budget: &Budget) -> OneToAll<W> {
+    let mut predecessors: Vec<(usize, usize)> = Vec::new();
+    let mut best: Vec<Option<(usize, W, W)>> = vec![None; graph.len()];
+    // menor consumo de los labels permanentes de cada nodo
+    let mut min_consumption = vec![W::MAX; graph.len()];
+
+    let mut heap: BinaryHeap<Entry<W>> = BinaryHeap::new();
+    heap.push(Reverse((Ordered(W::ZERO), Ordered(W::ZERO), s, 0)));
+    let mut iterations: u32 = 0;
+
+    while let Some(Reverse((Ordered(cost), Ordered(consumption), node, parent))) = heap.pop() {
+        iterations = iterations.wrapping_add(1);
+        if iterations.is_multiple_of(BUDGET_CHECK_INTERVAL) && budget.exhausted() {
+            return OneToAll {predecessors, best, status: PulseStatus::Interrupted};
+        }
+
+        // dominado por un label permanente más barato (o igual de caro) que consume lo mismo o menos
+        if consumption >= min_consumption[node] {
+            continue;
+        }
+        min_consumption[node] = consumption;
+        let label = predecessors.len();
+        predecessors.push((node, if node == s {label} else {parent}));
+        if best[node].is_none() {
+            best[node] = Some((label, cost, consumption));
+        }
+
+        for &(v, edge_cost, edge_consumption) in &graph[node] {
+            // una suma que desborda no cabe en el límite ni da un costo que se pueda comparar
+            let (Some(new_consumption), Some(new_cost)) = (consumption.checked_add(edge_consumption), cost.checked_add(edge_cost)) else { continue };
+            if new_consumption > resource_limit || new_consumption >= min_consumption[v] {
+                continue;
+            }
+            heap.push(Reverse((Ordered(new_cost), Ordered(new_consumption), v, label)));
+        }
+    }
+
+    OneToAll {predecessors, best, status: PulseStatus::Optimal}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{assert_optimal, from_edges, limits, random_graph};
+
+    #[test]
+    fn follows_a_later_label_when_the_first_does_not_fit() {
+        // a 1 se llega directo (1, 5) o por 2 (4, 2); a 3 con límite 5 solo por el segundo
+        let graph = from_edges(5, &[(0, 1, 1, 5), (0, 2, 2, 1), (2, 1, 2, 1), (1, 3, 1, 1)]);
+        let result = one_to_all(&graph, 0, 5, &Budget::unlimited());
+        assert_eq!((result.path(1), result.cost(1)), (Some(vec![0, 1]), Some((1, 5))));
+        assert_eq!((result.path(3), result.cost(3)), (Some(vec![0, 2, 1, 3]), Some((5, 3))));
+        // 4 no tiene arcos de entrada
+        assert_eq!((result.path(4), result.cost(4), result.reachable()), (None, None, 4));
+
+        let result = one_to_all(&graph, 0, 6, &Budget::unlimited());
+        assert_eq!(result.path(3), Some(vec![0, 1, 3]));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                let result = one_to_all(&graph, 0, limit, &Budget::unlimited());
+                assert_eq!(result.status, PulseStatus::Optimal);
+                assert_eq!((result.cost(0), result.path(0)), (Some((0, 0)), Some(vec![0])));
+                for v in 1..graph.len() {
+                    let path = result.path(v);
+                    let best = result.cost(v).map(|(cost, consumption)| (path.as_deref().unwrap(), cost, consumption));
+                    assert_optimal(&graph, 0, v, limit, best);
+                }
+            }
+        }
+    }
+}
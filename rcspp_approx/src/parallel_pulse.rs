@@ -0,0 +1,240 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::pulse_algorithm::{expand_pulse, initial_incumbent, Pulse, PulseContext, PulseOptions, PulseResult, PulseStats, PulseStatus, Step};
+use crate::weight::Weight;
+
+// Se abren niveles del árbol del pulso hasta tener al menos esta cantidad de
+// subproblemas por hilo, o hasta llegar a la profundidad máxima.
+const SUBPROBLEMS_PER_THREAD: usize = 8;
+const MAX_SPLIT_DEPTH: usize = 16;
+
+/// Camino parcial desde `s` que un hilo toma como raíz: (camino, costo, consumo)
+type Subproblem<W> = (Vec<usize>, W, W);
+
+/// Algoritmo del pulso con `threads` hilos.
+///
+/// Los primeros niveles del árbol del pulso se expanden en anchura y cada camino
+/// parcial resultante es un subproblema que algún hilo explora con `expand_pulse`.
+/// Los hilos comparten la cota primal (atómica) y el mejor camino (con un lock);
+/// los labels de dominancia son propios de cada hilo. La cota atómica guarda los bits
+/// de `Weight::to_bits`, que respetan el orden de los pesos no negativos.
+pub fn parallel_pulse<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, threads: usize, options: &PulseOptions<W>) -> PulseResult<Pulse<W>> {
+    let mut stats = PulseStats::new();
+    let ctx = PulseContext::new(graph, e, resource_limit).with_lagrangian_bound(s, e, options);
+    stats.bounds_time = ctx.bounds_time;
+    stats.lagrangian_time = ctx.lagrangian_time;
+    let (initial_bound, initial_path) = initial_incumbent(graph, s, e, resource_limit, options);
+    if initial_path.is_some() {
+        stats.record_incumbent(initial_bound);
+    }
+    let incumbent = Incumbent {bound: AtomicU64::new(initial_bound.to_bits()), path: Mutex::new(initial_path)};
+
+    let (mut subproblems, status) = split(&ctx, s, e, threads.max(1) * SUBPROBLEMS_PER_THREAD, options, &incumbent, &mut stats);
+    if status == PulseStatus::Interrupted {
+        return PulseResult {best: incumbent.path.into_inner().unwrap(), status, stats};
+    }
+    // primero los subproblemas con mejor cota inferior de costo
+    subproblems.sort_by(|(a, a_cost, _), (b, b_cost, _)| {
+        let bound = |path: &[usize], cost: &W| cost.add(ctx.minimum_cost[*path.last().unwrap()]);
+        bound(a, a_cost).total_cmp(&bound(b, b_cost))
+    });
+
+    let next = AtomicUsize::new(0);
+    let interrupted = AtomicBool::new(false);
+    let template = stats.fork();
+    let worker_stats: Vec<PulseStats> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1)).map(|worker| {
+            let (ctx, subproblems, next, interrupted) = (&ctx, &subproblems, &next, &interrupted);
+            let incumbent = &incumbent;
+            let mut stats = template.fork();
+            scope.spawn(move || {
+                // cada hilo tiene su propia secuencia aleatoria, derivada de la semilla común
+                let mut labels = options.labels.build(graph.len(), options.seed.wrapping_add(worker as u64));
+                while let Some((path, cost, consumption)) = subproblems.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let mut curr = Pulse::from_path(path.clone(), *cost, *consumption, graph.len());
+                    let mut local_bound = incumbent.bound();
+
+                    let status = expand_pulse(ctx, &options.budget, &mut local_bound, labels.as_mut(), &mut curr, &mut stats, |curr, local_bound, stats| {
+                        // la cota primal local se sincroniza con la compartida en cada pulso
+                        let shared = incumbent.bound();
+                        if shared < *local_bound {
+                            *local_bound = shared;
+                        }
+                        if curr.last_node == e {
+                            if curr.cost < *local_bound {
+                                *local_bound = curr.cost;
+                                if incumbent.update(curr) {
+                                    stats.record_incumbent(curr.cost);
+                                }
+                            }
+                            return Step::Stop;
+                        }
+                        Step::Expand
+                    });
+                    if status == PulseStatus::Interrupted {
+                        interrupted.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                }
+                stats
+            })
+        }).collect();
+        workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+    });
+    worker_stats.into_iter().for_each(|worker| stats.merge(worker));
+
+    let status = if interrupted.into_inner() {PulseStatus::Interrupted} else {PulseStatus::Optimal};
+    PulseResult {best: incumbent.path.into_inner().unwrap(), status, stats}
+}
+
+// Cota primal (atómica) y mejor camino (con un lock) que comparten los hilos
+struct Incumbent<W> {
+    bound: AtomicU64,
+    path: Mutex<Option<Pulse<W>>>,
+}
+
+impl<W: Weight> Incumbent<W> {
+    fn bound(&self) -> W {
+        W::from_bits(self.bound.load(Ordering::Relaxed))
+    }
+
+    // Guarda `curr` si es estrictamente más barato que la cota compartida, como pide
+    // `WarmStart::Bound`; devuelve si pasó a ser el mejor camino global
+    fn update(&self, curr: &Pulse<W>) -> bool {
+        if curr.cost >= self.bound() {
+            return false;
+        }
+        self.bound.fetch_min(curr.cost.to_bits(), Ordering::Relaxed);
+        let mut best = self.path.lock().unwrap();
+        if best.as_ref().is_none_or(|b| curr.cost < b.cost) {
+            *best = Some(curr.clone());
+            return true;
+        }
+        false
+    }
+}
+
+// Expande en anchura, nivel por nivel, los pulsos que salen de `s` hasta tener
+// `target` caminos parciales, con las mismas reglas de poda que `expand_pulse`. Los que
+// llegan a `e` actualizan el incumbente. El presupuesto se revisa una vez por nivel.
+fn split<W: Weight>(ctx: &PulseContext<W>, s: usize, e: usize, target: usize, options: &PulseOptions<W>, incumbent: &Incumbent<W>, stats: &mut PulseStats) -> (Vec<Subproblem<W>>, PulseStatus) {
+    let mut labels = options.labels.build(ctx.graph.len(), options.seed);
+    labels.update(s, W::ZERO, W::ZERO);
+    let mut level: Vec<Subproblem<W>> = vec![(vec![s], W::ZERO, W::ZERO)];
+
+    for _ in 0..MAX_SPLIT_DEPTH {
+        if level.is_empty() || level.len() >= target {
+            break;
+        }
+        if options.budget.exhausted() {
+            return (level, PulseStatus::Interrupted);
+        }
+
+        let mut next_level = Vec::new();
+        for (path, cost, consumption) in level {
+            let mut curr = Pulse::from_path(path, cost, consumption, ctx.graph.len());
+            for &edge in &ctx.graph[curr.last_node] {
+                if curr.visited[edge.0] {
+                    continue;
+                }
+
+                curr.add_edge(edge);
+                if let Some(prune) = curr.pruned_by(ctx, incumbent.bound(), labels.as_ref()) {
+                    stats.count(prune);
+                } else if curr.last_node == e {
+                    if incumbent.update(&curr) {
+                        stats.record_incumbent(curr.cost);
+                    }
+                } else {
+                    stats.expanded += 1;
+                    stats.max_depth = stats.max_depth.max(curr.path.len() - 1);
+                    labels.update(curr.last_node, curr.cost, curr.consumption);
+                    next_level.push((curr.path.clone(), curr.cost, curr.consumption));
+                }
+                curr.remove_edge(edge);
+            }
+        }
+        level = next_level;
+    }
+
+    (level, PulseStatus::Optimal)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::labels::LabelStrategy;
+    use crate::pulse_algorithm::{pulse_algorithm, Budget, WarmStart};
+    use crate::test_graphs::{assert_optimal, diamonds, is_feasible, layered_graph, limits, random_graph};
+
+    #[test]
+    fn split_opens_levels_until_every_thread_has_work() {
+        // 4 nodos por capa: con 4 hilos hacen falta 32 subproblemas, que salen de la tercera capa
+        let graph = layered_graph(0, 4, 6);
+        let e = graph.len() - 1;
+        let ctx = PulseContext::new(&graph, e, u32::MAX);
+        let incumbent = Incumbent {bound: AtomicU64::new(u32::MAX.to_bits()), path: Mutex::new(None)};
+        let (subproblems, status) = split(&ctx, 0, e, 4 * SUBPROBLEMS_PER_THREAD, &PulseOptions::default(), &incumbent, &mut PulseStats::new());
+        assert_eq!(status, PulseStatus::Optimal);
+        // los labels podan parte de la tercera capa, pero igual alcanza para los 4 hilos
+        assert!(subproblems.len() >= 4 * SUBPROBLEMS_PER_THREAD);
+        assert!(subproblems.iter().all(|(path, _, _)| path.len() == 4 && path[0] == 0));
+        assert_eq!(subproblems.iter().map(|(path, _, _)| path).collect::<HashSet<_>>().len(), subproblems.len());
+    }
+
+    #[test]
+    fn threads_share_the_bound_on_a_deep_split() {
+        // 4^7 caminos: el óptimo está muy por debajo de los niveles que abre la división
+        for seed in 0..3 {
+            let graph = layered_graph(seed, 4, 7);
+            let e = graph.len() - 1;
+            for limit in limits(&graph, 0, e) {
+                for threads in [2, 8] {
+                    let result = parallel_pulse(&graph, 0, e, limit, threads, &PulseOptions::default());
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    assert_optimal(&graph, 0, e, limit, result.best.as_ref().map(|best| (&best.path[..], best.cost, best.consumption)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exhausted_budget_returns_the_incumbent() {
+        let graph = diamonds(20);
+        let e = graph.len() - 1;
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = parallel_pulse(&graph, 0, e, u32::MAX / 2, 4, &PulseOptions {budget: budget.clone(), ..PulseOptions::default()});
+        assert_eq!(result.status, PulseStatus::Interrupted);
+        // la división se corta antes de llegar a `e`: solo queda el incumbente inicial
+        assert!(result.best.is_none());
+        let path = pulse_algorithm(&graph, 0, e, u32::MAX / 2, &PulseOptions::default()).best.unwrap().path;
+        let result = parallel_pulse(&graph, 0, e, u32::MAX / 2, 4, &PulseOptions {budget, warm_start: Some(WarmStart::Path(path.clone())), ..PulseOptions::default()});
+        assert_eq!(result.status, PulseStatus::Interrupted);
+        let best = result.best.unwrap();
+        assert_eq!(best.path, path);
+        assert!(is_feasible(&graph, 0, e, u32::MAX / 2, &best.path, best.cost, best.consumption));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                for (threads, labels, lagrangian) in [(1, LabelStrategy::Three, false), (4, LabelStrategy::Three, true), (4, LabelStrategy::Pareto { capacity: 2 }, false)] {
+                    let result = parallel_pulse(&graph, 0, 8, limit, threads, &PulseOptions {labels, lagrangian, ..PulseOptions::default()});
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    assert_optimal(&graph, 0, 8, limit, result.best.as_ref().map(|best| (&best.path[..], best.cost, best.consumption)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::pulse_algorithm::{expand_pulse, Prune, Pulse, PulseContext, PulseOptions, PulseStats, PulseStatus, Step};
+use crate::weight::Weight;
+
+/// Frente de Pareto (costo, consumo) de caminos de `s` a `e`, ordenado por costo
+/// creciente (y por lo tanto consumo decreciente).
+#[derive(Debug, Clone)]
+pub struct ParetoFront<W> {
+    pub paths: Vec<Pulse<W>>,
+    pub status: PulseStatus,
+    /// en `incumbents` queda cada punto que entró al frente
+    pub stats: PulseStats,
+}
+
+/// Algoritmo del pulso que junta en una sola búsqueda todos los caminos no dominados
+/// en (costo, consumo) con consumo a lo sumo `max_limit`.
+///
+/// Usa las mismas cotas y reglas de poda que `pulse_algorithm`, pero en lugar de una
+/// cota primal escalar un pulso se poda si algún punto del frente domina a
+/// (costo + costo mínimo, consumo + consumo mínimo) desde su último nodo. El frente
+/// con límite `L` es el sufijo de puntos con consumo <= `L`, así que basta una búsqueda
+/// para todas las restricciones de recurso hasta `max_limit`.
+pub fn pareto_pulse<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, max_limit: W, options: &PulseOptions<W>) -> ParetoFront<W> {
+    let mut curr = Pulse::start(s, graph.len());
+    let mut labels = options.labels.build(graph.len(), options.seed);
+    let mut stats = PulseStats::new();
+    // sin cota primal escalar la poda lagrangiana no actúa: no hace falta λ
+    let ctx = PulseContext::new(graph, e, max_limit);
+    stats.bounds_time = ctx.bounds_time;
+    let mut front: Vec<Pulse<W>> = Vec::new();
+
+    // sin cota primal escalar: la poda por costo la hace el frente
+    let mut primal_bound = W::MAX;
+    let status = expand_pulse(&ctx, &options.budget, &mut primal_bound, labels.as_mut(), &mut curr, &mut stats, |curr, _primal_bound, stats| {
+        if curr.last_node == e {
+            if insert(&mut front, curr) {
+                stats.record_incumbent(curr.cost);
+            }
+            return Step::Stop;
+        }
+
+        let cost = curr.cost.add(ctx.minimum_cost[curr.last_node]);
+        let consumption = curr.consumption.add(ctx.minimum_consumption[curr.last_node]);
+        if is_dominated(&front, cost, consumption) {
+            // el frente hace de cota primal
+            stats.count(Prune::Bound);
+            return Step::Stop;
+        }
+        Step::Expand
+    });
+
+    ParetoFront {paths: front, status, stats}
+}
+
+// ¿algún punto del frente tiene costo y consumo a lo sumo (`cost`, `consumption`)?
+fn is_dominated<W: Weight>(front: &[Pulse<W>], cost: W, consumption: W) -> bool {
+    // de los puntos con costo <= `cost`, el último es el de menor consumo
+    let pos = front.partition_point(|p| p.cost <= cost);
+    pos > 0 && front[pos - 1].consumption <= consumption
+}
+
+// agrega `curr` al frente si nadie lo domina; devuelve si entró
+fn insert<W: Weight>(front: &mut Vec<Pulse<W>>, curr: &Pulse<W>) -> bool {
+    if is_dominated(front, curr.cost, curr.consumption) {
+        return false;
+    }
+    front.retain(|p| p.cost < curr.cost || p.consumption < curr.consumption);
+    let pos = front.partition_point(|p| p.cost < curr.cost);
+    front.insert(pos, curr.clone());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labels::LabelStrategy;
+    use crate::test_graphs::{all_paths, from_edges, limits, random_graph};
+
+    fn points(front: &ParetoFront<u32>) -> Vec<(u32, u32)> {
+        front.paths.iter().map(|p| (p.cost, p.consumption)).collect()
+    }
+
+    #[test]
+    fn drops_dominated_paths_and_those_over_the_limit() {
+        // 0 → 2 → 4 (4, 6) está dominado por 0 → 1 → 4 (3, 5)
+        let graph = from_edges(5, &[
+            (0, 4, 1, 10),
+            (0, 1, 2, 2), (1, 4, 1, 3),
+            (0, 2, 2, 3), (2, 4, 2, 3),
+            (0, 3, 4, 1), (3, 4, 4, 1),
+        ]);
+        let front = |limit| pareto_pulse(&graph, 0, 4, limit, &PulseOptions::default());
+        assert_eq!(points(&front(10)), vec![(1, 10), (3, 5), (8, 2)]);
+        assert_eq!(front(10).paths[1].path, vec![0, 1, 4]);
+        assert_eq!(points(&front(9)), vec![(3, 5), (8, 2)]);
+        assert_eq!(points(&front(1)), vec![]);
+        // desde 0 → 2 lo mejor posible es (4, 6), que (3, 5) domina: lo poda el frente
+        assert_eq!(front(10).stats.pruned_by_bound, 1);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 8, 0.4);
+            let paths = all_paths(&graph, 0, 7);
+            for limit in limits(&graph, 0, 7) {
+                let mut expected: Vec<(u32, u32)> = paths.iter()
+                    .map(|&(_, cost, consumption)| (cost, consumption))
+                    .filter(|&(c, r)| r <= limit && !paths.iter().any(|&(_, c2, r2)| c2 <= c && r2 <= r && (c2, r2) != (c, r)))
+                    .collect();
+                expected.sort();
+                expected.dedup();
+                for labels in [LabelStrategy::Three, LabelStrategy::Pareto { capacity: 2 }] {
+                    let front = pareto_pulse(&graph, 0, 7, limit, &PulseOptions {labels, ..PulseOptions::default()});
+                    assert_eq!(front.status, PulseStatus::Optimal);
+                    assert_eq!(points(&front), expected, "límite {}, semilla {}", limit, seed);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::VecDeque;
+
+use crate::pulse_algorithm::{depth_first, get_bounds, Budget, PartialPath, PulseStatus, Step, BUDGET_CHECK_INTERVAL};
+use crate::weight::Weight;
+
+/// Arco con costo reducido (con signo): (nodo, costo reducido, consumo)
+pub type SignedEdge<W> = (usize, i64, W);
+
+/// Camino de costo reducido negativo, candidato a columna del maestro.
+#[derive(Debug, Clone)]
+pub struct Column<W> {
+    pub path: Vec<usize>,
+    pub reduced_cost: i64,
+    pub consumption: W,
+}
+
+/// Columnas encontradas, ordenadas por costo reducido creciente.
+#[derive(Debug, Clone)]
+pub struct PricingResult<W> {
+    pub columns: Vec<Column<W>>,
+    pub status: PulseStatus,
+    /// hay ciclos de costo reducido negativo desde los que se llega a `e`; la búsqueda sigue
+    /// siendo exacta, pero con una cota de costo más débil en los nodos que los alcanzan
+    pub negative_cycle: bool,
+}
+
+/// Construye el grafo de costos reducidos `c_uv - dual[u]` a partir del grafo original
+/// y de los duales de los nodos; `None` si algún costo no es entero.
+pub fn reduced_cost_graph<W: Weight>(graph: &[Vec<(usize, W, W)>], duals: &[i64]) -> Option<Vec<Vec<SignedEdge<W>>>> {
+    graph.iter().enumerate()
+        .map(|(u, adj)| adj.iter().map(|&(v, cost, cons)| Some((v, cost.to_i64()? - duals[u], cons))).collect())
+        .collect()
+}
+
+/// Pricing para branch-and-price: problema elemental de camino más corto con recurso (ESPPRC).
+///
+/// Los costos pueden ser negativos, así que la cota de costo hacia `e` sale de
+/// `get_signed_bounds` en lugar de `get_bounds`. Se devuelven hasta `max_columns` caminos
+/// elementales de costo reducido negativo, los mejores primero.
+///
+/// No se usan labels de dominancia: descartarían caminos dominados que igual pueden
+/// estar entre las `max_columns` columnas pedidas.
+pub fn pricing_pulse<W: Weight>(graph: &[Vec<SignedEdge<W>>], s: usize, e: usize, resource_limit: W, max_columns: usize, budget: &Budget) -> PricingResult<W> {
+    let mut columns: Vec<Column<W>> = Vec::new();
+    let Some(SignedBounds {minimum_cost, negative_cycle}) = get_signed_bounds(graph, e, budget) else {
+        return PricingResult {columns, status: PulseStatus::Interrupted, negative_cycle: false};
+    };
+    let consumption_graph: Vec<Vec<(usize, W, W)>> = graph.iter()
+        .map(|adj| adj.iter().map(|&(v, _cost, cons)| (v, W::ZERO, cons)).collect())
+        .collect();
+    let minimum_consumption = get_bounds(&consumption_graph, e, |(_a, _b, c)| c);
+
+    // solo interesan costos reducidos negativos; con `max_columns` columnas, la peor es la cota
+    let threshold = |columns: &[Column<W>]| -> i64 {
+        if columns.len() < max_columns {0} else {columns.last().unwrap().reduced_cost}
+    };
+    if max_columns == 0 || minimum_consumption[s] > resource_limit {
+        return PricingResult {columns, status: PulseStatus::Optimal, negative_cycle};
+    }
+
+    let mut curr = SignedPulse::start(s, graph.len());
+    let status = depth_first(graph, budget, &mut curr, |curr| {
+        let v = curr.last_node();
+        // reglas de poda: cota de costo (con signo) y factibilidad; la suma satura, así un
+        // desborde hacia abajo no poda y uno hacia arriba sí
+        if minimum_cost[v].is_none_or(|bound| curr.cost.saturating_add(bound) >= threshold(&columns))
+        || minimum_consumption[v] > resource_limit
+        || !curr.consumption.add_fits(minimum_consumption[v], resource_limit) {
+            return Step::Stop;
+        }
+
+        if v == e {
+            // la cota de `e` puede ser negativa si hay ciclos negativos que pasan por él
+            if curr.cost < threshold(&columns) {
+                let pos = columns.partition_point(|c| c.reduced_cost <= curr.cost);
+                columns.insert(pos, Column {path: curr.path.clone(), reduced_cost: curr.cost, consumption: curr.consumption});
+                columns.truncate(max_columns);
+            }
+            return Step::Stop;
+        }
+        Step::Expand
+    });
+
+    PricingResult {columns, status, negative_cycle}
+}
+
+// camino parcial del pricing, con costo reducido con signo
+struct SignedPulse<W> {
+    path: Vec<usize>,
+    cost: i64,
+    consumption: W,
+    visited: Vec<bool>,
+    // (costo, consumo) antes de cada arco agregado, como en `Pulse`
+    trail: Vec<(i64, W)>,
+}
+
+impl<W: Weight> SignedPulse<W> {
+    fn start(s: usize, num_nodes: usize) -> Self {
+        let mut visited = vec![false; num_nodes];
+        visited[s] = true;
+        SignedPulse {path: vec![s], cost: 0, consumption: W::ZERO, visited, trail: Vec::new()}
+    }
+}
+
+impl<W: Weight> PartialPath<SignedEdge<W>> for SignedPulse<W> {
+    fn last_node(&self) -> usize {
+        *self.path.last().unwrap()
+    }
+
+    fn closes_cycle(&self, edge: &SignedEdge<W>) -> bool {
+        self.visited[edge.0]
+    }
+
+    // un camino cuyo costo o consumo se desborda no puede ser columna: se poda
+    fn push_edge(&mut self, &(v, edge_cost, edge_cons): &SignedEdge<W>) -> bool {
+        let (Some(cost), Some(consumption)) = (self.cost.checked_add(edge_cost), self.consumption.checked_add(edge_cons)) else {
+            return false;
+        };
+        self.trail.push((self.cost, self.consumption));
+        self.path.push(v);
+        self.visited[v] = true;
+        (self.cost, self.consumption) = (cost, consumption);
+        true
+    }
+
+    fn pop_edge(&mut self, edge: &SignedEdge<W>) {
+        self.path.pop();
+        self.visited[edge.0] = false;
+        (self.cost, self.consumption) = self.trail.pop().expect("se quitó un arco que no se había agregado");
+    }
+}
+
+// cotas de costo de cada nodo hacia `e` y si hubo ciclos negativos
+struct SignedBounds {
+    minimum_cost: Vec<Option<i64>>,
+    negative_cycle: bool,
+}
+
+/// Cota inferior del costo de cualquier camino elemental de cada nodo a `e`, con costos
+/// de cualquier signo; `None` si `e` no es alcanzable. Devuelve `None` si se agota `budget`.
+///
+/// Es Bellman-Ford hacia `e` con una cola: solo se vuelven a revisar los arcos que llegan a
+/// un nodo cuya cota bajó. Sin ciclos negativos cada cota es el costo del mejor camino. Si
+/// la cota de un nodo sale de un paseo de `n` arcos o más, ese paseo tiene un ciclo negativo:
+/// ese nodo y todos los que llegan a él dejan de tener un mejor paseo, y su cota pasa a ser
+/// la suma de los arcos más negativos que salen de cada nodo, que ningún camino elemental
+/// puede bajar.
+fn get_signed_bounds<W: Weight>(graph: &[Vec<SignedEdge<W>>], e: usize, budget: &Budget) -> Option<SignedBounds> {
+    let mut graph_rev: Vec<Vec<(usize, i64)>> = vec![Vec::new(); graph.len()];
+    for (u, adj) in graph.iter().enumerate() {
+        for &(v, cost, _cons) in adj {
+            graph_rev[v].push((u, cost));
+        }
+    }
+
+    let mut bounds: Vec<Option<i64>> = vec![None; graph.len()];
+    // arcos del paseo que da la cota de cada nodo
+    let mut arcs = vec![0; graph.len()];
+    // nodos desde los que se llega a un ciclo negativo
+    let mut unbounded = vec![false; graph.len()];
+    let mut queued = vec![false; graph.len()];
+    let mut queue = VecDeque::from([e]);
+    bounds[e] = Some(0);
+    queued[e] = true;
+    let mut iterations: u32 = 0;
+
+    while let Some(v) = queue.pop_front() {
+        iterations = iterations.wrapping_add(1);
+        if iterations.is_multiple_of(BUDGET_CHECK_INTERVAL) && budget.exhausted() {
+            return None;
+        }
+        queued[v] = false;
+        if unbounded[v] {
+            continue;
+        }
+        let bound_v = bounds[v].unwrap();
+
+        for &(u, cost) in &graph_rev[v] {
+            if unbounded[u] {
+                continue;
+            }
+            let Some(bound) = bound_v.checked_add(cost) else { continue };
+            if bounds[u].is_some_and(|bound_u| bound_u <= bound) {
+                continue;
+            }
+            bounds[u] = Some(bound);
+            arcs[u] = arcs[v] + 1;
+            if arcs[u] >= graph.len() {
+                mark_unbounded(&graph_rev, u, &mut unbounded);
+            } else if !queued[u] {
+                queued[u] = true;
+                queue.push_back(u);
+            }
+        }
+    }
+
+    let negative_cycle = unbounded.iter().any(|&unbounded| unbounded);
+    if negative_cycle {
+        let floor: i64 = graph.iter().enumerate()
+            .filter(|&(u, _adj)| u != e)
+            .filter_map(|(_u, adj)| adj.iter().map(|&(_v, cost, _cons)| cost).min())
+            .filter(|&cost| cost < 0)
+            .fold(0, i64::saturating_add);
+        for (bound, _) in bounds.iter_mut().zip(&unbounded).filter(|(_, &unbounded)| unbounded) {
+            *bound = Some(floor);
+        }
+    }
+
+    Some(SignedBounds {minimum_cost: bounds, negative_cycle})
+}
+
+// marca `u` y todos los nodos que llegan a él
+fn mark_unbounded(graph_rev: &[Vec<(usize, i64)>], u: usize, unbounded: &mut [bool]) {
+    let mut stack = vec![u];
+    unbounded[u] = true;
+    while let Some(v) = stack.pop() {
+        for &(prev, _cost) in &graph_rev[v] {
+            if !unbounded[prev] {
+                unbounded[prev] = true;
+                stack.push(prev);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{all_paths, from_edges, random_graph};
+
+    #[test]
+    fn subtracts_the_dual_of_the_tail() {
+        let graph = from_edges(3, &[(0, 1, 5, 1), (1, 2, 4, 2)]);
+        let reduced = reduced_cost_graph(&graph, &[1, 10, 0]).unwrap();
+        assert_eq!(reduced, vec![vec![(1, 4, 1)], vec![(2, -6, 2)], vec![]]);
+    }
+
+    #[test]
+    fn prices_through_a_negative_cycle() {
+        // 1 → 2 → 1 es un ciclo de costo -4 desde el que se llega a 3: la cota de 1 y 2 no
+        // puede ser la de un mejor paseo, pero los caminos elementales siguen siendo exactos
+        let graph: Vec<Vec<SignedEdge<u32>>> = vec![
+            vec![(1, 1, 1), (3, -1, 5)],
+            vec![(2, -3, 1), (3, 2, 1)],
+            vec![(1, -1, 1), (3, -2, 1)],
+            vec![],
+        ];
+        let result = pricing_pulse(&graph, 0, 3, 10, 10, &Budget::unlimited());
+        assert!(result.negative_cycle);
+        assert_eq!(result.status, PulseStatus::Optimal);
+        let columns: Vec<_> = result.columns.iter().map(|c| (c.path.clone(), c.reduced_cost, c.consumption)).collect();
+        assert_eq!(columns, vec![(vec![0, 1, 2, 3], -4, 3), (vec![0, 3], -1, 5)]);
+
+        // con poco recurso solo queda 0 → 1 → 3, de costo reducido positivo; con una sola columna, la mejor
+        let result = pricing_pulse(&graph, 0, 3, 2, 10, &Budget::unlimited());
+        assert_eq!(result.columns.iter().map(|c| c.path.clone()).collect::<Vec<_>>(), Vec::<Vec<usize>>::new());
+        let result = pricing_pulse(&graph, 0, 3, 3, 1, &Budget::unlimited());
+        assert_eq!(result.columns.iter().map(|c| c.path.clone()).collect::<Vec<_>>(), vec![vec![0, 1, 2, 3]]);
+
+        let acyclic: Vec<Vec<SignedEdge<u32>>> = vec![vec![(1, 1, 1)], vec![(2, -3, 1)], vec![]];
+        assert!(!pricing_pulse(&acyclic, 0, 2, 10, 10, &Budget::unlimited()).negative_cycle);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 8, 0.4);
+            // duales altos en la mitad de los nodos: aparecen arcos y ciclos negativos
+            let duals: Vec<i64> = (0..8).map(|v| if (v + seed as usize).is_multiple_of(2) {25} else {0}).collect();
+            let reduced = reduced_cost_graph(&graph, &duals).unwrap();
+            let reduced_cost = |path: &[usize]| -> i64 {
+                path.windows(2).map(|uv| reduced[uv[0]].iter().find(|edge| edge.0 == uv[1]).unwrap().1).sum()
+            };
+            let paths = all_paths(&graph, 0, 7);
+            for limit in [20, 60, 200] {
+                for max_columns in [1, 3, 50] {
+                    let mut expected: Vec<i64> = paths.iter()
+                        .filter(|(_, _, consumption)| *consumption <= limit)
+                        .map(|(path, _, _)| reduced_cost(path))
+                        .filter(|&cost| cost < 0)
+                        .collect();
+                    expected.sort();
+                    expected.truncate(max_columns);
+
+                    let result = pricing_pulse(&reduced, 0, 7, limit, max_columns, &Budget::unlimited());
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    for column in &result.columns {
+                        let &(_, _, consumption) = paths.iter().find(|(path, _, _)| *path == column.path).unwrap();
+                        assert_eq!((reduced_cost(&column.path), consumption), (column.reduced_cost, column.consumption));
+                        assert!(consumption <= limit);
+                    }
+                    let found: Vec<i64> = result.columns.iter().map(|c| c.reduced_cost).collect();
+                    assert_eq!(found, expected, "límite {}, {} columnas, semilla {}", limit, max_columns, seed);
+                }
+            }
+        }
+    }
+}
@@ -1,167 +1,537 @@
-use std::{cmp::Reverse, collections::BinaryHeap};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+// cada cuántos pulsos se revisa el presupuesto de tiempo
+pub(crate) const BUDGET_CHECK_INTERVAL: u32 = 1024;
+
+// holgura relativa de la poda lagrangiana, que se calcula en `f64`
+const LAGRANGIAN_TOLERANCE: f64 = 1e-9;
+
+use crate::labels::{LabelStore, LabelStrategy};
+use crate::larac;
+use crate::weight::{Ordered, Weight};
 
 #[derive(Debug, Clone)]
-pub struct Pulse {
+pub struct Pulse<W> {
     pub path: Vec<usize>,
-    pub cost: u32,
-    pub consumption: u32,
+    pub cost: W,
+    pub consumption: W,
     pub last_node: usize,
     pub visited: Vec<bool>,
+    // (costo, consumo) antes de cada arco agregado: deshacerlo no resta, así no se
+    // acumula error de redondeo con pesos `f64`
+    trail: Vec<(W, W)>,
 }
 
-impl Pulse {
-    fn new(path: Vec<usize>, cost: u32, consumption: u32, last_node: usize, visited: Vec<bool>) -> Self {
-        Pulse {path, cost, consumption, last_node, visited}
+impl<W: Weight> Pulse<W> {
+    pub(crate) fn new(path: Vec<usize>, cost: W, consumption: W, last_node: usize, visited: Vec<bool>) -> Self {
+        Pulse {path, cost, consumption, last_node, visited, trail: Vec::new()}
     }
 
-    fn add_edge(&mut self, edge: (usize, u32, u32)) {
+    // pulso inicial parado en `s`, sin arcos recorridos
+    pub(crate) fn start(s: usize, num_nodes: usize) -> Self {
+        let mut visited = vec![false; num_nodes];
+        visited[s] = true;
+        Pulse::new(vec![s], W::ZERO, W::ZERO, s, visited)
+    }
+
+    // arma un pulso a partir de un camino completo ya conocido
+    pub(crate) fn from_path(path: Vec<usize>, cost: W, consumption: W, num_nodes: usize) -> Self {
+        let mut visited = vec![false; num_nodes];
+        path.iter().for_each(|&node| visited[node] = true);
+        let last_node = *path.last().unwrap();
+        Pulse::new(path, cost, consumption, last_node, visited)
+    }
+
+    pub(crate) fn add_edge(&mut self, edge: (usize, W, W)) {
+        self.trail.push((self.cost, self.consumption));
         self.path.push(edge.0);
         self.visited[edge.0] = true;
         self.last_node = edge.0;
-        self.cost += edge.1;
-        self.consumption += edge.2;
+        // un desborde queda en `W::MAX`, que las reglas de poda descartan
+        self.cost = self.cost.checked_add(edge.1).unwrap_or(W::MAX);
+        self.consumption = self.consumption.checked_add(edge.2).unwrap_or(W::MAX);
     }
 
-    fn remove_edge(&mut self, edge: (usize, u32, u32)) {
+    pub(crate) fn remove_edge(&mut self, edge: (usize, W, W)) {
         self.path.pop();
         self.visited[edge.0] = false;
         self.last_node = *self.path.last().unwrap();
-        self.cost -= edge.1;
-        self.consumption -= edge.2;
-        
+        (self.cost, self.consumption) = self.trail.pop().expect("se quitó un arco que no se había agregado");
     }
 
     // reglas de poda
-    fn is_dominated(&self, other: & [Option<(u32,u32)>; 3]) -> bool {
-        let mut is_dom: u8 = 0;
+    pub(crate) fn check_bounds(&self, primal_bound: W, minimum_cost: &[W]) -> bool {
+        minimum_cost[self.last_node] < primal_bound && self.cost.add_fits(minimum_cost[self.last_node], primal_bound)
+    }
+
+    pub(crate) fn check_feasibility(&self, resource_limit: W, minimum_consumption: &[W]) -> bool {
+        minimum_consumption[self.last_node] <= resource_limit && self.consumption.add_fits(minimum_consumption[self.last_node], resource_limit)
+    }
+
+    // cota lagrangiana: para cualquier camino factible que complete el pulso,
+    // costo >= costo + λ·consumo + min_{costo+λ·recurso} - λ·límite
+    pub(crate) fn check_lagrangian_bound(&self, primal_bound: W, ctx: &PulseContext<W>) -> bool {
+        if ctx.lambda == 0.0 || primal_bound == W::MAX {
+            return true;
+        }
+        let bound = self.cost.to_f64() + ctx.lambda * self.consumption.to_f64()
+            + ctx.minimum_combined[self.last_node] - ctx.lambda * ctx.resource_limit.to_f64();
+        let primal_bound = primal_bound.to_f64();
+        bound <= primal_bound + LAGRANGIAN_TOLERANCE * primal_bound.abs().max(1.0)
+    }
+
+    // aplica las reglas de poda sobre el último nodo del pulso y dice cuál lo podó
+    pub(crate) fn pruned_by(&self, ctx: &PulseContext<W>, primal_bound: W, labels: &dyn LabelStore<W>) -> Option<Prune> {
+        if labels.is_dominated(self.last_node, self.cost, self.consumption) {
+            Some(Prune::Dominance)
+        } else if !self.check_bounds(primal_bound, &ctx.minimum_cost) {
+            Some(Prune::Bound)
+        } else if !self.check_feasibility(ctx.resource_limit, &ctx.minimum_consumption) {
+            Some(Prune::Infeasibility)
+        } else if !self.check_lagrangian_bound(primal_bound, ctx) {
+            Some(Prune::Lagrangian)
+        } else {
+            None
+        }
+    }
+}
+
+/// Presupuesto de una búsqueda: un instante límite opcional y una bandera de
+/// cancelación que se puede activar desde otro hilo (las copias la comparten).
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Budget::default()
+    }
+
+    pub fn timeout(limit: Duration) -> Self {
+        Budget {deadline: Some(Instant::now() + limit), ..Budget::default()}
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn exhausted(&self) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            // así las demás copias del presupuesto no tienen que volver a mirar el reloj
+            self.cancel();
+            return true;
+        }
+        false
+    }
+}
+
+/// Opciones comunes a los algoritmos de pulso.
+#[derive(Debug, Clone, Default)]
+pub struct PulseOptions<W> {
+    pub budget: Budget,
+    pub labels: LabelStrategy,
+    /// semilla de toda la aleatoriedad de la búsqueda, para poder reproducirla
+    pub seed: u64,
+    /// incumbente inicial; lo usan los pulsos de un recurso (secuencial, paralelo y bidireccional)
+    pub warm_start: Option<WarmStart<W>>,
+    /// poda con la cota lagrangiana en los pulsos de un recurso; cuesta una corrida de LARAC
+    /// y una pasada más de `get_bounds` antes de empezar, por eso va apagada por defecto
+    pub lagrangian: bool,
+}
+
+/// Incumbente con el que arranca la búsqueda en lugar de `W::MAX`.
+#[derive(Debug, Clone)]
+pub enum WarmStart<W> {
+    /// un camino de `s` a `e` ya conocido (por ejemplo, de una heurística); se ignora
+    /// si no es un camino elemental del grafo o no cabe en el límite de recursos
+    Path(Vec<usize>),
+    /// solo una cota superior del costo: se buscan caminos estrictamente más baratos
+    Bound(W),
+}
+
+/// Cómo terminó una búsqueda exacta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PulseStatus {
+    /// se exploró todo el árbol: el camino devuelto es óptimo (o no hay camino factible)
+    Optimal,
+    /// se acabó el presupuesto: el camino devuelto es el mejor encontrado hasta ese momento
+    Interrupted,
+}
+
+/// Resultado de los algoritmos de pulso: el incumbente, si se probó su optimalidad
+/// y las estadísticas de la búsqueda.
+#[derive(Debug, Clone)]
+pub struct PulseResult<P> {
+    pub best: Option<P>,
+    pub status: PulseStatus,
+    pub stats: PulseStats,
+}
+
+/// Regla de poda que descartó un pulso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Prune {
+    Dominance,
+    Bound,
+    Lagrangian,
+    Infeasibility,
+}
 
-        for i in 0..3 {
-            match other[i] {
-                Some((cost, consumption)) => if self.consumption >= consumption && self.cost >= cost {is_dom+=1},
-                None => ()
-            } 
+/// Contadores de una búsqueda del pulso, para ver dónde se va el tiempo.
+#[derive(Debug, Clone)]
+pub struct PulseStats {
+    /// pulsos que pasaron las podas y se siguieron propagando
+    pub expanded: u64,
+    pub pruned_by_dominance: u64,
+    /// podas por `costo + costo mínimo` contra la cota primal (en `pareto_pulse`, contra
+    /// los puntos del frente)
+    pub pruned_by_bound: u64,
+    pub pruned_by_lagrangian: u64,
+    pub pruned_by_infeasibility: u64,
+    /// (tiempo desde el inicio de la búsqueda, costo) de cada mejora del incumbente
+    pub incumbents: Vec<(Duration, f64)>,
+    /// máxima cantidad de arcos de un pulso
+    pub max_depth: usize,
+    /// tiempo calculando las cotas de costo y consumo mínimos (`get_bounds`)
+    pub bounds_time: Duration,
+    /// tiempo calculando el multiplicador de Lagrange (LARAC) y la cota combinada
+    pub lagrangian_time: Duration,
+    started: Instant,
+}
+
+impl PulseStats {
+    pub(crate) fn new() -> Self {
+        PulseStats {
+            expanded: 0,
+            pruned_by_dominance: 0,
+            pruned_by_bound: 0,
+            pruned_by_lagrangian: 0,
+            pruned_by_infeasibility: 0,
+            incumbents: Vec::new(),
+            max_depth: 0,
+            bounds_time: Duration::ZERO,
+            lagrangian_time: Duration::ZERO,
+            started: Instant::now(),
         }
+    }
+
+    // estadísticas vacías con el mismo instante de inicio, para otro hilo de la misma búsqueda
+    pub(crate) fn fork(&self) -> Self {
+        PulseStats {started: self.started, ..PulseStats::new()}
+    }
 
-        is_dom > 2
+    pub(crate) fn count(&mut self, prune: Prune) {
+        match prune {
+            Prune::Dominance => self.pruned_by_dominance += 1,
+            Prune::Bound => self.pruned_by_bound += 1,
+            Prune::Lagrangian => self.pruned_by_lagrangian += 1,
+            Prune::Infeasibility => self.pruned_by_infeasibility += 1,
+        }
     }
 
-    fn check_bounds(&self, primal_bound: u32, minimum_cost: &Vec<u32>) -> bool {
-        minimum_cost[self.last_node] < primal_bound && self.cost + minimum_cost[self.last_node] <= primal_bound
+    pub(crate) fn record_incumbent<W: Weight>(&mut self, cost: W) {
+        self.incumbents.push((self.started.elapsed(), cost.to_f64()));
     }
 
-    fn check_feasibility(&self, resource_limit: u32, minimum_consumption: &Vec<u32>) -> bool {
-        minimum_consumption[self.last_node] <= resource_limit && self.consumption + minimum_consumption[self.last_node] <= resource_limit
+    // suma las estadísticas de otra búsqueda sobre el mismo problema (otro hilo, otra fase)
+    pub(crate) fn merge(&mut self, other: PulseStats) {
+        self.expanded += other.expanded;
+        self.pruned_by_dominance += other.pruned_by_dominance;
+        self.pruned_by_bound += other.pruned_by_bound;
+        self.pruned_by_lagrangian += other.pruned_by_lagrangian;
+        self.pruned_by_infeasibility += other.pruned_by_infeasibility;
+        self.incumbents.extend(other.incumbents);
+        self.incumbents.sort_by_key(|&(time, _)| time);
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.bounds_time += other.bounds_time;
+        self.lagrangian_time += other.lagrangian_time;
     }
+}
 
-    // para actualizar los lables, esta es una manera, la otra es tener multiples labels por nodo
-    fn replace_first(&self, other: Option<(u32,u32)>) -> bool {
-        match other {
-            Some((cost, _consumption)) => self.cost < cost,
-            None => true
+/// Datos de solo lectura de una búsqueda de pulso: el grafo, el destino y las
+/// cotas inferiores (costo, consumo y costo lagrangiano mínimos) de cada nodo hacia el destino.
+pub(crate) struct PulseContext<'a, W> {
+    pub graph: &'a [Vec<(usize, W, W)>],
+    pub resource_limit: W,
+    pub minimum_cost: Vec<W>,
+    pub minimum_consumption: Vec<W>,
+    /// multiplicador de Lagrange de la restricción de recurso (0 si no aporta o no se pidió)
+    pub lambda: f64,
+    /// mínimo de `costo + λ·recurso` de cada nodo hacia el destino; vacío si `lambda` es 0
+    pub minimum_combined: Vec<f64>,
+    /// lo que tardaron las cotas de costo y consumo mínimos
+    pub bounds_time: Duration,
+    /// lo que tardaron λ (LARAC) y la cota combinada
+    pub lagrangian_time: Duration,
+}
+
+impl<'a, W: Weight> PulseContext<'a, W> {
+    /// Cotas de costo y consumo mínimos hacia `e`, sin poda lagrangiana.
+    pub(crate) fn new(graph: &'a [Vec<(usize, W, W)>], e: usize, resource_limit: W) -> Self {
+        let start = Instant::now();
+        let minimum_consumption = get_bounds(graph, e, |(_a, _b,c)| c);
+        let minimum_cost = get_bounds(graph, e, |(_a, b, _c)| b);
+
+        PulseContext {
+            graph,
+            resource_limit,
+            minimum_cost,
+            minimum_consumption,
+            lambda: 0.0,
+            minimum_combined: Vec::new(),
+            bounds_time: start.elapsed(),
+            lagrangian_time: Duration::ZERO,
         }
     }
 
-    fn replace_second(&self, other: Option<(u32,u32)>) -> bool {
-        match other {
-            Some((_cost, consumption)) => self.consumption < consumption,
-            None => true
+    /// Activa la poda lagrangiana si `options` la pide, con λ de LARAC entre `s` y `e`.
+    pub(crate) fn with_lagrangian_bound(self, s: usize, e: usize, options: &PulseOptions<W>) -> Self {
+        if !options.lagrangian {
+            return self;
         }
+        let start = Instant::now();
+        let lambda = lagrangian_multiplier(self.graph, s, e, self.resource_limit);
+        let mut ctx = self.with_multiplier(lambda, e);
+        ctx.lagrangian_time = start.elapsed();
+        ctx
     }
 
-    fn replace_third(&self, other: Option<(u32,u32)>) -> bool {
-        match other {
-            Some((_cost, _consumption)) => rand::random_bool(0.5),
-            None => true
+    /// Poda lagrangiana con un λ ya calculado (por ejemplo, el del grafo sin invertir): hace
+    /// falta una tercera pasada de `get_bounds`, sobre el peso combinado costo + λ·recurso.
+    pub(crate) fn with_multiplier(mut self, lambda: f64, e: usize) -> Self {
+        let start = Instant::now();
+        self.lambda = lambda;
+        if lambda > 0.0 {
+            let combined: Vec<Vec<(usize, f64, f64)>> = self.graph.iter()
+                .map(|adj| adj.iter().map(|&(v, cost, res)| (v, cost.to_f64() + lambda * res.to_f64(), 0.0)).collect())
+                .collect();
+            self.minimum_combined = get_bounds(&combined, e, |(_a, b, _c)| b);
         }
+        self.lagrangian_time = start.elapsed();
+        self
     }
 }
 
 
-pub fn pulse_algorithm(graph: &Vec<Vec<(usize, u32, u32)>>, s: usize, e:usize, resource_limit: u32)-> Option<Pulse> {
+pub fn pulse_algorithm<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e:usize, resource_limit: W, options: &PulseOptions<W>)-> PulseResult<Pulse<W>> {
     //every edge is (node, cost, consumption)
-    let mut visited = vec![false; graph.len()];
-    visited[s] = true;
-    let mut curr = Pulse::new(vec![s], 0, 0, s, visited);
-    let mut labels: Vec<[Option<(u32, u32)>; 3]> = vec![[None,None,None]; graph.len()];
-
-    let mut primal_bound = u32::MAX;
-    let minimum_consumption = get_bounds(&graph, e, |(_a, _b,c)| c);
-    //println!("Minimum consumption: {:?}", minimum_consumption);
-    let minimum_cost = get_bounds(&graph, e, |(_a, b, _c)| b);
-    //println!("Minimum cost: {:?}", minimum_cost);
-    let mut best_path = None;
+    let mut curr = Pulse::start(s, graph.len());
+    let mut labels = options.labels.build(graph.len(), options.seed);
+
+    let mut stats = PulseStats::new();
+    let ctx = PulseContext::new(graph, e, resource_limit).with_lagrangian_bound(s, e, options);
+    stats.bounds_time = ctx.bounds_time;
+    stats.lagrangian_time = ctx.lagrangian_time;
+    let (mut primal_bound, mut best_path) = initial_incumbent(graph, s, e, resource_limit, options);
+    if best_path.is_some() {
+        stats.record_incumbent(primal_bound);
+    }
     
-    expand_pulse(&graph, s, e, resource_limit, &mut primal_bound, &minimum_cost, &minimum_consumption, &mut labels, &mut curr, &mut best_path);
+    let status = expand_pulse(&ctx, &options.budget, &mut primal_bound, labels.as_mut(), &mut curr, &mut stats, |curr, primal_bound, stats| {
+        //if we get to the end, it updates the primal bound and the best path
+        if curr.last_node == e {
+            if curr.cost < *primal_bound {
+                *primal_bound = curr.cost;
+                best_path = Some(curr.clone());
+                stats.record_incumbent(curr.cost);
+            }
+            return Step::Stop;
+        }
+        Step::Expand
+    });
 
-    best_path
+    PulseResult {best: best_path, status, stats}
 }
 
 
-fn expand_pulse(graph: &Vec<Vec<(usize, u32, u32)>>, 
-            s: usize, 
-            e: usize, 
-            resource_limit: u32, 
-            primal_bound: &mut u32, 
-            minimum_cost: &Vec<u32>, 
-            minimum_consumption: &Vec<u32>, 
-            labels: &mut Vec<[Option<(u32,u32)>; 3]>,
-            curr: &mut Pulse,
-            best_path: &mut Option<Pulse>) {
-    
-    // uses backtracking with prunning strategies to find the best path
-    
+/// Cota primal y mejor camino con los que arranca la búsqueda, según `options.warm_start`.
+pub(crate) fn initial_incumbent<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, options: &PulseOptions<W>) -> (W, Option<Pulse<W>>) {
+    match &options.warm_start {
+        None => (W::MAX, None),
+        Some(WarmStart::Bound(bound)) => (*bound, None),
+        Some(WarmStart::Path(path)) => match path_pulse(graph, s, e, path) {
+            Some(pulse) if pulse.consumption <= resource_limit => (pulse.cost, Some(pulse)),
+            _ => (W::MAX, None),
+        },
+    }
+}
 
-    //println!("lables: {:?}", labels);
-    if curr.replace_first(labels[curr.last_node][0]) {
-        labels[curr.last_node][0] = Some((curr.cost, curr.consumption))
+// Pulso que recorre `path`, si es un camino elemental de `s` a `e` en el grafo.
+// Entre arcos paralelos se toma el más barato.
+fn path_pulse<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, path: &[usize]) -> Option<Pulse<W>> {
+    if path.first() != Some(&s) || path.last() != Some(&e) || path.iter().any(|&v| v >= graph.len()) {
+        return None;
     }
 
-    if curr.replace_second(labels[curr.last_node][1]) {
-        labels[curr.last_node][1] = Some((curr.cost, curr.consumption))
+    let mut pulse = Pulse::start(s, graph.len());
+    for uv in path.windows(2) {
+        let &edge = graph[uv[0]].iter()
+            .filter(|edge| edge.0 == uv[1])
+            .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))?;
+        if pulse.visited[edge.0] {
+            return None;
+        }
+        pulse.add_edge(edge);
+    }
+    Some(pulse)
+}
+
+/// Qué hacer con un pulso que sobrevivió a las reglas de poda.
+pub(crate) enum Step {
+    /// seguir propagando el pulso desde su último nodo
+    Expand,
+    /// no propagarlo más (llegó al destino, se guardó, etc.)
+    Stop,
+}
+
+/// Camino parcial que `depth_first` extiende y recorta arco por arco.
+pub(crate) trait PartialPath<E> {
+    fn last_node(&self) -> usize;
+
+    /// ¿El arco vuelve a un nodo del camino?
+    fn closes_cycle(&self, edge: &E) -> bool;
+
+    /// Agrega el arco al final del camino; `false` (sin agregarlo) si no se puede tomar.
+    fn push_edge(&mut self, edge: &E) -> bool;
+
+    /// Quita el último arco del camino, que es `edge`.
+    fn pop_edge(&mut self, edge: &E);
+}
+
+impl<W: Weight> PartialPath<(usize, W, W)> for Pulse<W> {
+    fn last_node(&self) -> usize {
+        self.last_node
     }
 
-    if curr.replace_third(labels[curr.last_node][2]) {
-        labels[curr.last_node][2] = Some((curr.cost, curr.consumption))
+    fn closes_cycle(&self, edge: &(usize, W, W)) -> bool {
+        self.visited[edge.0]
     }
-    //println!("curr: {:?}", curr);
-    //println!("labels: {:?}", labels);
-    for &edge in &graph[curr.last_node] {
-        if curr.visited[edge.0] {
-            continue;
+
+    fn push_edge(&mut self, edge: &(usize, W, W)) -> bool {
+        self.add_edge(*edge);
+        true
+    }
+
+    fn pop_edge(&mut self, edge: &(usize, W, W)) {
+        self.remove_edge(*edge);
+    }
+}
+
+// Un marco por nodo del camino actual: el arco por el que se llegó y el índice
+// del siguiente arco de salida por explorar. Vive en el heap, no en la pila del hilo.
+struct Frame<'a, E> {
+    edge: Option<&'a E>,
+    next: usize,
+}
+
+/// Búsqueda en profundidad desde `curr` con una pila explícita de marcos,
+/// de modo que caminos de miles de nodos no desborden la pila del hilo.
+/// Cada camino elemental que se arma al agregar un arco se entrega a `visit`, que
+/// aplica las reglas de poda y decide si el camino se sigue propagando.
+/// Si se agota `budget` la búsqueda se corta y devuelve `PulseStatus::Interrupted`.
+pub(crate) fn depth_first<E, P, F>(graph: &[Vec<E>], budget: &Budget, curr: &mut P, mut visit: F) -> PulseStatus
+where
+    P: PartialPath<E>,
+    F: FnMut(&P) -> Step,
+{
+    let mut stack = vec![Frame { edge: None, next: 0 }];
+    let mut iterations: u32 = 0;
+
+    while let Some(frame) = stack.last_mut() {
+        iterations = iterations.wrapping_add(1);
+        if iterations.is_multiple_of(BUDGET_CHECK_INTERVAL) && budget.exhausted() {
+            return PulseStatus::Interrupted;
         }
 
-        curr.add_edge(edge);
+        let adj = &graph[curr.last_node()];
+        if frame.next == adj.len() {
+            // se agotaron los arcos de este nodo: backtracking
+            if let Some(edge) = frame.edge {
+                curr.pop_edge(edge);
+            }
+            stack.pop();
+            continue;
+        }
 
-        if curr.is_dominated(&labels[curr.last_node]) 
-        || !curr.check_bounds(*primal_bound, minimum_cost) 
-        || !curr.check_feasibility(resource_limit, minimum_consumption) {
-            curr.remove_edge(edge);
+        let edge = &adj[frame.next];
+        frame.next += 1;
+        if curr.closes_cycle(edge) || !curr.push_edge(edge) {
             continue;
         }
-            
-        //if we get to the end, it updates the primal bound and the best path
-        if curr.last_node == e  && curr.cost < *primal_bound{
-            *primal_bound = curr.cost;
-            *best_path = Some(curr.clone());
-        } else if curr.last_node != e {
-            //if we are not at the end, we call the function recursively
-            expand_pulse(graph, s, e, resource_limit, primal_bound, minimum_cost, minimum_consumption, labels, curr, best_path);
+
+        match visit(curr) {
+            Step::Expand => stack.push(Frame { edge: Some(edge), next: 0 }),
+            Step::Stop => curr.pop_edge(edge),
         }
-            
-        curr.remove_edge(edge);
-            
     }
-    
+
+    PulseStatus::Optimal
+}
+
+/// Búsqueda en profundidad del pulso desde `curr` (ver `depth_first`) con las reglas de
+/// poda del pulso de un recurso. Cada pulso que pasa las reglas de poda se entrega a
+/// `visit`, que puede actualizar la cota primal y decide si el pulso se sigue propagando.
+/// Las podas, los pulsos expandidos y la profundidad se cuentan en `stats`.
+pub(crate) fn expand_pulse<W, F>(ctx: &PulseContext<W>,
+            budget: &Budget,
+            primal_bound: &mut W, 
+            labels: &mut dyn LabelStore<W>,
+            curr: &mut Pulse<W>,
+            stats: &mut PulseStats,
+            mut visit: F) -> PulseStatus
+where
+    W: Weight,
+    F: FnMut(&Pulse<W>, &mut W, &mut PulseStats) -> Step,
+{
+    // uses backtracking with prunning strategies to find the best path
+    labels.update(curr.last_node, curr.cost, curr.consumption);
+    depth_first(ctx.graph, budget, curr, |curr| {
+        if let Some(prune) = curr.pruned_by(ctx, *primal_bound, labels) {
+            stats.count(prune);
+            return Step::Stop;
+        }
+        stats.max_depth = stats.max_depth.max(curr.path.len() - 1);
+
+        let step = visit(curr, primal_bound, stats);
+        if let Step::Expand = step {
+            stats.expanded += 1;
+            labels.update(curr.last_node, curr.cost, curr.consumption);
+        }
+        step
+    })
 }
     
 
+/// Grafo con todos los arcos invertidos, conservando (costo, consumo).
+pub(crate) fn reverse_graph<W: Weight>(graph: &[Vec<(usize, W, W)>]) -> Vec<Vec<(usize, W, W)>> {
+    let mut graph_rev = vec![Vec::new(); graph.len()];
+    for (u, adj) in graph.iter().enumerate() {
+        for &(v, cost, consumption) in adj {
+            graph_rev[v].push((u, cost, consumption));
+        }
+    }
+    graph_rev
+}
+
 
-fn get_bounds(graph: &Vec<Vec<(usize, u32, u32)>>, s: usize, cost: fn((usize, u32,u32))->u32)-> Vec<u32> {
+pub(crate) fn get_bounds<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, cost: fn((usize, W, W))->W)-> Vec<W> {
     // Reverse graph for Dijkstra's algorithm so we can find the minimum cost to each node from the target node
     // so we also get the minimum cost from every node to the target node
     // with the function cost, it also give us a way to get the minimum consumption without reapeating code
     let graph_rev = graph.iter().enumerate().fold(vec![Vec::new(); graph.len()],
-     |mut acc: Vec<Vec<(usize, u32)>>, (i, adj)| {
+     |mut acc: Vec<Vec<(usize, W)>>, (i, adj)| {
         adj.iter().for_each(|&edge| {
             acc[edge.0].push((i, cost(edge)));
             
@@ -170,26 +540,244 @@ fn get_bounds(graph: &Vec<Vec<(usize, u32, u32)>>, s: usize, cost: fn((usize, u3
         acc
     });
 
-    let mut heap: BinaryHeap<Reverse<(u32, usize)>> = BinaryHeap::new();
-    heap.push(Reverse((0, s))); // (costo acumulado, consumo acumulado, nodo actual)
-    let mut min_consumption = vec![u32::MAX; graph.len()];
-    min_consumption[s] = 0;
+    let mut heap: BinaryHeap<Reverse<(Ordered<W>, usize)>> = BinaryHeap::new();
+    heap.push(Reverse((Ordered(W::ZERO), s))); // (costo acumulado, consumo acumulado, nodo actual)
+    let mut min_consumption = vec![W::MAX; graph.len()];
+    min_consumption[s] = W::ZERO;
 
-    while let Some(Reverse((resource, node))) = heap.pop() {
+    while let Some(Reverse((Ordered(resource), node))) = heap.pop() {
         if resource > min_consumption[node] {
             continue;
         }
 
         for &(next_node, edge_resource) in &graph_rev[node] {
 
-            let new_resource = resource + edge_resource;
+            let Some(new_resource) = resource.checked_add(edge_resource) else {
+                continue;
+            };
 
             if new_resource < min_consumption[next_node] {
                 min_consumption[next_node] = new_resource;
-                heap.push(Reverse((new_resource, next_node)));
+                heap.push(Reverse((Ordered(new_resource), next_node)));
             }
         }
     }
 
     min_consumption
 }
+
+
+/// Multiplicador λ >= 0 que maximiza la cota dual `min(costo + λ·recurso) - λ·límite`,
+/// el mismo que encuentra LARAC. Devuelve 0 si el camino de mínimo costo ya es factible
+/// o si no hay camino factible.
+pub(crate) fn lagrangian_multiplier<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W) -> f64 {
+    larac::larac(graph, s, e, resource_limit).map_or(0.0, |result| result.lambda)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{all_paths, assert_optimal, diamonds, from_edges, is_feasible, limits, optimum, random_graph};
+
+    #[test]
+    fn skips_cheaper_paths_that_do_not_fit() {
+        // el arco directo es el más barato pero el que más consume
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let path = |limit| pulse_algorithm(&graph, 0, 3, limit, &PulseOptions::default()).best.map(|best| best.path);
+        assert_eq!(path(10), Some(vec![0, 3]));
+        assert_eq!(path(4), Some(vec![0, 1, 3]));
+        assert_eq!(path(2), Some(vec![0, 2, 3]));
+        assert_eq!(path(1), None);
+    }
+
+    #[test]
+    fn paths_whose_sums_overflow_are_infeasible() {
+        // por 1 el costo desborda y por 3 el consumo; solo queda 0 → 4 → 2
+        let max = u32::MAX;
+        let graph = from_edges(5, &[(0, 1, max - 1, 1), (1, 2, 5, 1), (0, 3, 1, max - 1), (3, 2, 1, 5), (0, 4, 10, 10), (4, 2, 10, 10)]);
+        let best = pulse_algorithm(&graph, 0, 2, max, &PulseOptions::default()).best.unwrap();
+        assert_eq!((best.path, best.cost), (vec![0, 4, 2], 20));
+    }
+
+    #[test]
+    fn long_paths_do_not_overflow_the_stack() {
+        // una cadena de 100 000 arcos: con recursión desbordaría la pila del hilo de prueba
+        let n = 100_000;
+        let edges: Vec<_> = (1..n).map(|v| (v - 1, v, 1, 1)).collect();
+        let graph = from_edges(n, &edges);
+        let best = pulse_algorithm(&graph, 0, n - 1, n as u32, &PulseOptions::default()).best.unwrap();
+        assert_eq!((best.path.len(), best.cost), (n, n as u32 - 1));
+    }
+
+    #[test]
+    fn exhausted_budget_returns_the_incumbent() {
+        // 2^20 caminos del mismo costo: el primero se encuentra enseguida y el resto no se poda
+        let graph = diamonds(20);
+        let e = graph.len() - 1;
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = pulse_algorithm(&graph, 0, e, u32::MAX / 2, &PulseOptions {budget, ..PulseOptions::default()});
+        assert_eq!(result.status, PulseStatus::Interrupted);
+        let best = result.best.unwrap();
+        assert!(is_feasible(&graph, 0, e, u32::MAX / 2, &best.path, best.cost, best.consumption));
+
+        let result = pulse_algorithm(&diamonds(6), 0, 18, 9, &PulseOptions {budget: Budget::timeout(Duration::from_secs(60)), ..PulseOptions::default()});
+        assert_eq!(result.status, PulseStatus::Optimal);
+        assert_eq!(result.best.map(|best| (best.cost, best.consumption <= 9)), Some((12, true)));
+    }
+
+    #[test]
+    fn same_seed_same_path() {
+        for seed in 0..10 {
+            let graph = random_graph(seed, 10, 0.4);
+            let limit = limits(&graph, 0, 9)[2];
+            let options = PulseOptions {seed, ..PulseOptions::default()};
+            let path = || pulse_algorithm(&graph, 0, 9, limit, &options).best.map(|best| best.path);
+            assert_eq!(path(), path());
+        }
+    }
+
+    #[test]
+    fn every_weight_type_finds_the_same_path() {
+        // los mismos datos en `u64` y en `f64` (escalados por 0.5) dan el mismo óptimo que en `u32`
+        for seed in 0..10 {
+            let graph = random_graph(seed, 8, 0.4);
+            let wide: Vec<Vec<(usize, u64, u64)>> = graph.iter()
+                .map(|adj| adj.iter().map(|&(v, cost, cons)| (v, cost as u64, cons as u64)).collect())
+                .collect();
+            let halves: Vec<Vec<(usize, f64, f64)>> = graph.iter()
+                .map(|adj| adj.iter().map(|&(v, cost, cons)| (v, cost as f64 / 2.0, cons as f64 / 2.0)).collect())
+                .collect();
+            for limit in limits(&graph, 0, 7) {
+                let narrow = pulse_algorithm(&graph, 0, 7, limit, &PulseOptions::default()).best.map(|best| best.cost);
+                let wide = pulse_algorithm(&wide, 0, 7, limit as u64, &PulseOptions::default()).best.map(|best| best.cost as u32);
+                let halves = pulse_algorithm(&halves, 0, 7, limit as f64 / 2.0, &PulseOptions::default()).best.map(|best| (best.cost * 2.0) as u32);
+                assert_eq!((wide, halves), (narrow, narrow), "límite {}, semilla {}", limit, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn lagrangian_bound_closes_the_gap_on_a_small_case() {
+        // el camino más barato (0 → 3) no cabe; λ = 1/2 iguala 0 → 3 y 0 → 1 → 3
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let ctx = PulseContext::new(&graph, 3, 4).with_lagrangian_bound(0, 3, &PulseOptions {lagrangian: true, ..PulseOptions::default()});
+        assert_eq!(ctx.lambda, 0.5);
+        // min(costo + λ·recurso) - λ·límite = 6 - 2 = 4, el costo del óptimo
+        assert_eq!(ctx.minimum_combined[0] - ctx.lambda * 4.0, 4.0);
+        // si el más barato cabe, el multiplicador no aporta
+        assert_eq!(PulseContext::new(&graph, 3, 10).with_lagrangian_bound(0, 3, &PulseOptions {lagrangian: true, ..PulseOptions::default()}).lambda, 0.0);
+    }
+
+    #[test]
+    fn lagrangian_bound_never_exceeds_the_optimum() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                let ctx = PulseContext::new(&graph, 7, limit).with_lagrangian_bound(0, 7, &PulseOptions {lagrangian: true, ..PulseOptions::default()});
+                let Some(optimum) = optimum(&graph, 0, 7, limit) else { continue };
+                // sin multiplicador no hay cota combinada
+                if ctx.lambda == 0.0 {
+                    assert!(ctx.minimum_combined.is_empty());
+                    continue;
+                }
+                let bound = ctx.minimum_combined[0] - ctx.lambda * limit as f64;
+                assert!(ctx.lambda >= 0.0 && bound <= optimum as f64 + 1e-6, "cota {} > óptimo {}", bound, optimum);
+            }
+        }
+    }
+
+    #[test]
+    fn lagrangian_bound_only_when_asked() {
+        // con λ = 1/2 la cota lagrangiana del pulso 0 → 2 es 6.5 > 5, aunque pasa las de costo y consumo
+        let graph = from_edges(5, &[(0, 1, 2, 2), (1, 4, 3, 3), (0, 4, 2, 11), (0, 2, 2, 1), (2, 4, 2, 9), (2, 3, 5, 1), (3, 4, 5, 1)]);
+        let run = |lagrangian| pulse_algorithm(&graph, 0, 4, 5, &PulseOptions {lagrangian, ..PulseOptions::default()});
+        let (off, on) = (run(false), run(true));
+        assert_eq!((off.stats.pruned_by_lagrangian, off.stats.lagrangian_time), (0, Duration::ZERO));
+        assert_eq!(on.stats.pruned_by_lagrangian, 1);
+        assert_eq!(off.best.map(|best| best.path), Some(vec![0, 1, 4]));
+        assert_eq!(on.best.map(|best| best.path), Some(vec![0, 1, 4]));
+    }
+
+    #[test]
+    fn warm_start_is_never_worse_than_its_path() {
+        for seed in 0..20 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                let Some(optimum) = optimum(&graph, 0, 7, limit) else { continue };
+                // el camino factible más caro como incumbente inicial
+                let (path, cost, _) = all_paths(&graph, 0, 7).into_iter()
+                    .filter(|&(_, _, consumption)| consumption <= limit)
+                    .max_by_key(|&(_, cost, _)| cost)
+                    .unwrap();
+                let options = PulseOptions {warm_start: Some(WarmStart::Path(path)), ..PulseOptions::default()};
+                let best = pulse_algorithm(&graph, 0, 7, limit, &options).best.unwrap();
+                assert!(best.cost <= cost);
+                assert_eq!(best.cost, optimum);
+            }
+        }
+    }
+
+    #[test]
+    fn warm_start_bounds_are_strict_and_bad_paths_are_ignored() {
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let run = |warm_start| pulse_algorithm(&graph, 0, 3, 4, &PulseOptions {warm_start: Some(warm_start), ..PulseOptions::default()}).best.map(|best| best.path);
+        // el óptimo cuesta 4: con esa cota no hay nada estrictamente más barato
+        assert_eq!(run(WarmStart::Bound(5)), Some(vec![0, 1, 3]));
+        assert_eq!(run(WarmStart::Bound(4)), None);
+        // el incumbente óptimo se devuelve tal cual
+        assert_eq!(run(WarmStart::Path(vec![0, 1, 3])), Some(vec![0, 1, 3]));
+        // infactible, sin arco o que no va de `s` a `e`: se ignoran
+        for ignored in [vec![0, 3], vec![0, 2, 1, 3], vec![1, 3], vec![0, 1, 9]] {
+            assert_eq!(run(WarmStart::Path(ignored)), Some(vec![0, 1, 3]));
+        }
+    }
+
+    #[test]
+    fn stats_count_every_pulse_once() {
+        // 0 → 3 no cabe, 0 → 1 → 3 es el óptimo y después 0 → 2 ya no puede mejorarlo
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let stats = pulse_algorithm(&graph, 0, 3, 4, &PulseOptions::default()).stats;
+        assert_eq!(
+            (stats.expanded, stats.pruned_by_infeasibility, stats.pruned_by_bound, stats.pruned_by_dominance, stats.pruned_by_lagrangian),
+            (1, 1, 1, 0, 0)
+        );
+        assert_eq!(stats.incumbents.iter().map(|&(_, cost)| cost).collect::<Vec<_>>(), vec![4.0]);
+        assert_eq!(stats.max_depth, 2);
+
+        // en los rombos nada se poda: se expanden los 14 nodos de arriba y abajo de cada
+        // prefijo y los 6 nodos intermedios, y los 8 caminos llegan a `e`
+        let stats = pulse_algorithm(&diamonds(3), 0, 9, 100, &PulseOptions::default()).stats;
+        let pruned = stats.pruned_by_dominance + stats.pruned_by_bound + stats.pruned_by_lagrangian + stats.pruned_by_infeasibility;
+        assert_eq!((stats.expanded, pruned, stats.incumbents.len(), stats.max_depth), (20, 0, 1, 6));
+    }
+
+    #[test]
+    fn incumbents_improve_until_the_optimum() {
+        for seed in 0..20 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                let result = pulse_algorithm(&graph, 0, 7, limit, &PulseOptions::default());
+                let incumbents = &result.stats.incumbents;
+                assert!(incumbents.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 > pair[1].1));
+                assert_eq!(incumbents.last().map(|&(_, cost)| cost), result.best.map(|best| best.cost as f64));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            for limit in limits(&graph, 0, 7) {
+                for labels in [LabelStrategy::Three, LabelStrategy::Pareto { capacity: 2 }] {
+                    for lagrangian in [false, true] {
+                        let result = pulse_algorithm(&graph, 0, 7, limit, &PulseOptions {labels, lagrangian, ..PulseOptions::default()});
+                        assert_eq!(result.status, PulseStatus::Optimal);
+                        assert_optimal(&graph, 0, 7, limit, result.best.as_ref().map(|best| (&best.path[..], best.cost, best.consumption)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::detour::{detour_tree, nodes, totals, tree_edges, Detour, Neighborhood};
+use crate::pulse_algorithm::{get_bounds, Budget};
+use crate::weight::Weight;
+
+/// Parámetros de la búsqueda tabú.
+#[derive(Debug, Clone, Copy)]
+pub struct TabuParams {
+    pub iterations: usize,
+    /// iteraciones que un arco quitado del camino no puede volver a entrar
+    pub tenure: usize,
+}
+
+/// Mejor camino factible que encontró la búsqueda tabú.
+#[derive(Debug, Clone)]
+pub struct TabuResult<W> {
+    pub best: Option<(Vec<usize>, W, W)>,
+    /// movimientos aplicados
+    pub iterations: usize,
+    /// si se acabó el presupuesto antes de hacer todas las iteraciones (o de quedarse sin
+    /// movimientos admisibles)
+    pub interrupted: bool,
+}
+
+/// Búsqueda tabú sobre caminos factibles.
+///
+/// Arranca del camino de mínimo consumo (factible si lo dice la cota de `get_bounds`) y en cada
+/// iteración aplica el mejor vecino factible aunque empeore: un vecino quita el arco que
+/// sale de un nodo del camino y re-rutea el tramo hasta un nodo posterior (a lo sumo
+/// `detour::SEGMENT_HOPS` arcos más adelante) por el desvío más barato que cabe en la holgura de
+/// recurso. Los arcos que salen del camino quedan tabú durante `tenure` iteraciones: un
+/// desvío que los usa solo se admite si mejora al incumbente (criterio de aspiración).
+pub fn tabu_search<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, params: &TabuParams, budget: &Budget) -> TabuResult<W> {
+    let minimum_consumption = get_bounds(graph, e, |(_a, _b, c)| c);
+    if minimum_consumption[s] > resource_limit {
+        return TabuResult {best: None, iterations: 0, interrupted: false};
+    }
+    // la cota ya dice que el camino de mínimo consumo es factible; falta armarlo
+    let tree = detour_tree(graph, s, |_cost, consumption| consumption, |_, _| false, resource_limit, &[e], usize::MAX);
+    // sin él, o si su costo desborda, no hay desde dónde arrancar
+    let Some((mut edges, (cost, consumption))) = tree_edges(&tree, s, e).and_then(|edges| totals(graph, s, &edges).map(|totals| (edges, totals))) else {
+        return TabuResult {best: None, iterations: 0, interrupted: false};
+    };
+    let mut best = (nodes(graph, s, &edges), cost, consumption);
+    // arco (nodo, índice) -> iteración en que deja de ser tabú
+    let mut tabu: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for iteration in 0..params.iterations {
+        tabu.retain(|_, expiry| *expiry > iteration);
+        let Some(next) = best_move(graph, s, &edges, resource_limit, &tabu, best.1, budget) else {
+            return TabuResult {best: Some(best), iterations: iteration, interrupted: budget.exhausted()};
+        };
+
+        // los arcos del tramo que no siguen en el desvío quedan tabú
+        let path = nodes(graph, s, &edges);
+        let detour_nodes = nodes(graph, path[next.from], &next.edges);
+        let kept: HashSet<(usize, usize)> = detour_nodes.iter().copied().zip(next.edges.iter().copied()).collect();
+        for (&node, &index) in path[next.from..next.to].iter().zip(&edges[next.from..next.to]) {
+            if !kept.contains(&(node, index)) {
+                tabu.insert((node, index), iteration + params.tenure);
+            }
+        }
+        edges.splice(next.from..next.to, next.edges);
+
+        if next.cost < best.1 {
+            best = (nodes(graph, s, &edges), next.cost, next.consumption);
+        }
+    }
+
+    TabuResult {best: Some(best), iterations: params.iterations, interrupted: false}
+}
+
+// El mejor vecino factible admisible (no tabú, o tabú pero mejor que `incumbent`), aunque
+// empeore el camino actual
+fn best_move<W: Weight>(
+    graph: &[Vec<(usize, W, W)>],
+    s: usize,
+    edges: &[usize],
+    resource_limit: W,
+    tabu: &HashMap<(usize, usize), usize>,
+    incumbent: W,
+    budget: &Budget
+) -> Option<Detour<W>> {
+    let neighborhood = Neighborhood::new(graph, s, edges, resource_limit)?;
+    let uses_tabu = |detour: &Detour<W>| {
+        nodes(graph, neighborhood.path[detour.from], &detour.edges).iter().zip(&detour.edges)
+            .any(|(&node, &index)| tabu.contains_key(&(node, index)))
+    };
+
+    let mut best: Option<Detour<W>> = None;
+    for (from, &index) in edges.iter().enumerate() {
+        if budget.exhausted() {
+            return None;
+        }
+        let removed = (neighborhood.path[from], index);
+
+        // primero sin los arcos tabú; si hay alguno, otra vez con ellos para la aspiración
+        for allow_tabu in [false, true] {
+            if allow_tabu && tabu.is_empty() {
+                break;
+            }
+            let forbidden = |node: usize, index: usize| (node, index) == removed || (!allow_tabu && tabu.contains_key(&(node, index)));
+            let promising = |cost: W| best.as_ref().is_none_or(|best| cost < best.cost) && (!allow_tabu || cost < incumbent);
+            for detour in neighborhood.detours(from, forbidden, promising) {
+                if best.as_ref().is_some_and(|best| best.cost <= detour.cost) {
+                    continue;
+                }
+                // aspiración: un desvío tabú solo entra si mejora al incumbente
+                if allow_tabu && (!uses_tabu(&detour) || detour.cost >= incumbent) {
+                    continue;
+                }
+                best = Some(detour);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{from_edges, is_feasible, limits, optimum, random_graph};
+
+    const PARAMS: TabuParams = TabuParams {iterations: 30, tenure: 3};
+
+    #[test]
+    fn returns_only_feasible_paths() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 9, 0.4);
+            for limit in limits(&graph, 0, 8) {
+                let result = tabu_search(&graph, 0, 8, limit, &PARAMS, &Budget::unlimited());
+                assert!(!result.interrupted);
+                let opt = optimum(&graph, 0, 8, limit);
+                // arranca del camino de mínimo consumo: hay resultado si hay algún factible
+                assert_eq!(result.best.is_some(), opt.is_some(), "semilla {} límite {}", seed, limit);
+                if let Some((path, cost, consumption)) = &result.best {
+                    assert!(is_feasible(&graph, 0, 8, limit, path, *cost, *consumption), "semilla {} límite {}", seed, limit);
+                    assert!(opt <= Some(*cost));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn moves_to_a_cheaper_parallel_arc() {
+        // el camino de mínimo consumo usa el arco 0 -> 1 caro; el paralelo barato cabe con límite 4
+        let graph = from_edges(3, &[(0, 1, 5, 1), (0, 1, 1, 3), (1, 2, 1, 1)]);
+        let result = tabu_search(&graph, 0, 2, 4, &PARAMS, &Budget::unlimited());
+        assert_eq!(result.best, Some((vec![0, 1, 2], 2, 4)));
+        let result = tabu_search(&graph, 0, 2, 3, &PARAMS, &Budget::unlimited());
+        assert_eq!(result.best, Some((vec![0, 1, 2], 6, 2)));
+    }
+
+    #[test]
+    fn exhausted_budget_returns_the_minimum_consumption_path() {
+        let graph = from_edges(4, &[(0, 3, 1, 10), (0, 1, 2, 2), (1, 3, 2, 2), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let result = tabu_search(&graph, 0, 3, 10, &PARAMS, &budget);
+        assert!(result.interrupted);
+        assert_eq!((result.best, result.iterations), (Some((vec![0, 2, 3], 6, 2)), 0));
+        assert_eq!(tabu_search(&graph, 0, 3, 10, &PARAMS, &Budget::unlimited()).best, Some((vec![0, 3], 1, 10)));
+    }
+}
@@ -0,0 +1,144 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Grafo de prueba: (nodo, costo, consumo) por arco.
+pub(crate) type Graph = Vec<Vec<(usize, u32, u32)>>;
+
+/// Camino elemental con su costo y consumo.
+pub(crate) type Path = (Vec<usize>, u32, u32);
+
+/// Grafo dirigido al azar de `n` nodos: el camino `0 → 1 → … → n-1` (así siempre hay
+/// camino de 0 a n-1) más cada arco `u → v` con probabilidad `density`.
+pub(crate) fn random_graph(seed: u64, n: usize, density: f64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut graph: Graph = vec![Vec::new(); n];
+    for (u, adj) in graph.iter_mut().enumerate() {
+        for v in 0..n {
+            if u != v && (v == u + 1 || rng.random_bool(density)) {
+                adj.push((v, rng.random_range(1..=30), rng.random_range(1..=30)));
+            }
+        }
+    }
+    graph
+}
+
+/// Grafo por capas: `s = 0`, `depth` capas de `width` nodos con todos los arcos entre capas
+/// consecutivas y `e` al final, así hay `width^depth` caminos de la misma cantidad de arcos.
+pub(crate) fn layered_graph(seed: u64, width: usize, depth: usize) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = width * depth + 2;
+    let layer = |i: usize| 1 + i * width..1 + (i + 1) * width;
+    let mut graph: Graph = vec![Vec::new(); n];
+    let mut connect = |u: usize, v: usize, rng: &mut StdRng| graph[u].push((v, rng.random_range(1..=30), rng.random_range(1..=30)));
+    for v in layer(0) {
+        connect(0, v, &mut rng);
+    }
+    for i in 1..depth {
+        for u in layer(i - 1) {
+            for v in layer(i) {
+                connect(u, v, &mut rng);
+            }
+        }
+    }
+    for u in layer(depth - 1) {
+        connect(u, n - 1, &mut rng);
+    }
+    graph
+}
+
+/// `k` rombos seguidos de `0` a `3k`: en cada uno se elige el lado de arriba (consumo 2) o el
+/// de abajo (consumo 1), ambos de costo 2. Los `2^k` caminos cuestan lo mismo y, en el orden
+/// de la búsqueda en profundidad, cada uno consume a lo sumo lo que los anteriores, así que
+/// ni la cota primal ni la dominancia podan: la búsqueda completa recorre todos.
+pub(crate) fn diamonds(k: usize) -> Graph {
+    let mut edges = Vec::new();
+    for i in 0..k {
+        let (u, up, down, w) = (3 * i, 3 * i + 1, 3 * i + 2, 3 * i + 3);
+        edges.extend([(u, up, 1, 2), (u, down, 1, 1), (up, w, 1, 0), (down, w, 1, 0)]);
+    }
+    from_edges(3 * k + 1, &edges)
+}
+
+/// Grafo de `n` nodos con los arcos `(u, v, costo, consumo)` dados, para casos armados a mano.
+pub(crate) fn from_edges(n: usize, edges: &[(usize, usize, u32, u32)]) -> Graph {
+    let mut graph: Graph = vec![Vec::new(); n];
+    for &(u, v, cost, consumption) in edges {
+        graph[u].push((v, cost, consumption));
+    }
+    graph
+}
+
+/// Todos los caminos elementales de `s` a `e` (con al menos un arco).
+pub(crate) fn all_paths(graph: &Graph, s: usize, e: usize) -> Vec<Path> {
+    let mut paths = Vec::new();
+    let mut path = vec![s];
+    enumerate(graph, e, &mut path, 0, 0, &mut paths);
+    paths
+}
+
+fn enumerate(graph: &Graph, e: usize, path: &mut Vec<usize>, cost: u32, consumption: u32, paths: &mut Vec<Path>) {
+    for &(v, edge_cost, edge_consumption) in &graph[*path.last().unwrap()] {
+        if path.contains(&v) {
+            continue;
+        }
+        path.push(v);
+        if v == e {
+            paths.push((path.clone(), cost + edge_cost, consumption + edge_consumption));
+        } else {
+            enumerate(graph, e, path, cost + edge_cost, consumption + edge_consumption, paths);
+        }
+        path.pop();
+    }
+}
+
+/// Costo del camino más barato de `s` a `e` con consumo a lo sumo `limit`, por enumeración.
+pub(crate) fn optimum(graph: &Graph, s: usize, e: usize, limit: u32) -> Option<u32> {
+    all_paths(graph, s, e).into_iter()
+        .filter(|&(_, _, consumption)| consumption <= limit)
+        .map(|(_, cost, _)| cost)
+        .min()
+}
+
+/// (costo, consumo) de `path` si es un camino elemental del grafo de `s` a `e`; entre
+/// arcos paralelos toma el primero, así que solo sirve para grafos sin ellos.
+pub(crate) fn evaluate(graph: &Graph, s: usize, e: usize, path: &[usize]) -> Option<(u32, u32)> {
+    if path.first() != Some(&s) || path.last() != Some(&e) || path.len() < 2 {
+        return None;
+    }
+    let mut seen = vec![false; graph.len()];
+    let (mut cost, mut consumption) = (0, 0);
+    for uv in path.windows(2) {
+        if seen[uv[0]] {
+            return None;
+        }
+        seen[uv[0]] = true;
+        let &(_, edge_cost, edge_consumption) = graph[uv[0]].iter().find(|edge| edge.0 == uv[1])?;
+        cost += edge_cost;
+        consumption += edge_consumption;
+    }
+    if seen[*path.last().unwrap()] {
+        return None;
+    }
+    Some((cost, consumption))
+}
+
+/// ¿`path` es un camino elemental de `s` a `e` con ese costo y consumo, dentro del límite?
+pub(crate) fn is_feasible(graph: &Graph, s: usize, e: usize, limit: u32, path: &[usize], cost: u32, consumption: u32) -> bool {
+    evaluate(graph, s, e, path) == Some((cost, consumption)) && consumption <= limit
+}
+
+/// Límites de recurso de prueba: desde el consumo mínimo (apenas factible) hasta uno holgado.
+pub(crate) fn limits(graph: &Graph, s: usize, e: usize) -> Vec<u32> {
+    let paths = all_paths(graph, s, e);
+    let min = paths.iter().map(|p| p.2).min().unwrap_or(0);
+    let max = paths.iter().map(|p| p.2).max().unwrap_or(0);
+    vec![min.saturating_sub(1), min, (min + max) / 2, max]
+}
+
+/// Comprueba que `best` (camino, costo, consumo) sea óptimo con el límite dado, o que no
+/// haya camino factible si es `None`.
+pub(crate) fn assert_optimal(graph: &Graph, s: usize, e: usize, limit: u32, best: Option<(&[usize], u32, u32)>) {
+    if let Some((path, cost, consumption)) = best {
+        assert!(is_feasible(graph, s, e, limit, path, cost, consumption), "camino infactible {:?} con límite {}", path, limit);
+    }
+    assert_eq!(best.map(|(_, cost, _)| cost), optimum(graph, s, e, limit), "límite {} en {:?}", limit, graph);
+}
@@ -0,0 +1,264 @@
+use std::{collections::BinaryHeap, time::Instant};
+
+use crate::pulse_algorithm::{depth_first, get_bounds, PartialPath, Prune, PulseOptions, PulseResult, PulseStats, PulseStatus, Step};
+use crate::weight::{Ordered, Weight};
+
+/// Pulso cuyo recurso es el tiempo: llegar a un nodo antes de su ventana obliga a esperar.
+#[derive(Debug, Clone)]
+pub struct WindowPulse<W> {
+    pub path: Vec<usize>,
+    pub cost: W,
+    /// instante de inicio de servicio en cada nodo del camino (ya con la espera)
+    pub times: Vec<W>,
+    pub last_node: usize,
+    pub visited: Vec<bool>,
+    // costo antes de cada arco agregado, como en `Pulse`
+    trail: Vec<W>,
+}
+
+impl<W: Weight> WindowPulse<W> {
+    fn start(s: usize, num_nodes: usize, windows: &[(W, W)]) -> Self {
+        let mut visited = vec![false; num_nodes];
+        visited[s] = true;
+        WindowPulse {path: vec![s], cost: W::ZERO, times: vec![windows[s].0], last_node: s, visited, trail: Vec::new()}
+    }
+
+    pub fn time(&self) -> W {
+        *self.times.last().unwrap()
+    }
+
+    // si se llega antes de que abra la ventana, el servicio empieza cuando abre
+    fn add_edge(&mut self, edge: (usize, W, W), windows: &[(W, W)]) {
+        let (lower, _upper) = windows[edge.0];
+        // un desborde queda en `W::MAX`, que ninguna ventana admite ni ninguna cota deja pasar
+        let arrival = self.time().checked_add(edge.2).unwrap_or(W::MAX);
+
+        self.trail.push(self.cost);
+        self.path.push(edge.0);
+        self.visited[edge.0] = true;
+        self.last_node = edge.0;
+        self.cost = self.cost.checked_add(edge.1).unwrap_or(W::MAX);
+        self.times.push(if arrival < lower {lower} else {arrival});
+    }
+
+    fn remove_edge(&mut self, edge: (usize, W, W)) {
+        self.path.pop();
+        self.times.pop();
+        self.visited[edge.0] = false;
+        self.last_node = *self.path.last().unwrap();
+        self.cost = self.trail.pop().expect("se quitó un arco que no se había agregado");
+    }
+
+    // reglas de poda
+    fn check_bounds(&self, primal_bound: W, minimum_cost: &[W]) -> bool {
+        minimum_cost[self.last_node] < primal_bound && self.cost.add_fits(minimum_cost[self.last_node], primal_bound)
+    }
+
+    fn check_feasibility(&self, latest: &[Option<W>]) -> bool {
+        latest[self.last_node].is_some_and(|latest| self.time() <= latest)
+    }
+
+    // ¿se llegó al último nodo después del cierre de su ventana?
+    fn is_late(&self, windows: &[(W, W)]) -> bool {
+        self.time() > windows[self.last_node].1
+    }
+}
+
+// pulso junto con las ventanas que necesita para agregar arcos
+struct WindowPath<'a, W> {
+    pulse: WindowPulse<W>,
+    windows: &'a [(W, W)],
+}
+
+impl<W: Weight> PartialPath<(usize, W, W)> for WindowPath<'_, W> {
+    fn last_node(&self) -> usize {
+        self.pulse.last_node
+    }
+
+    fn closes_cycle(&self, edge: &(usize, W, W)) -> bool {
+        self.pulse.visited[edge.0]
+    }
+
+    fn push_edge(&mut self, edge: &(usize, W, W)) -> bool {
+        self.pulse.add_edge(*edge, self.windows);
+        true
+    }
+
+    fn pop_edge(&mut self, edge: &(usize, W, W)) {
+        self.pulse.remove_edge(*edge);
+    }
+}
+
+/// Algoritmo del pulso con ventanas de tiempo (SPPRCTW).
+///
+/// `windows[v]` es el intervalo [inferior, superior] del nodo `v`: si se llega antes del
+/// inferior se espera hasta él y si se llega después del superior el camino es infactible.
+/// `resource_limit` sigue siendo el tiempo máximo de llegada a `e`.
+pub fn time_window_pulse<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, windows: &[(W, W)], options: &PulseOptions<W>) -> PulseResult<WindowPulse<W>> {
+    let mut stats = PulseStats::new();
+    let start = Instant::now();
+    let minimum_cost = get_bounds(graph, e, |(_a, b, _c)| b);
+    let latest = get_latest_times(graph, e, resource_limit, windows);
+    stats.bounds_time = start.elapsed();
+
+    let mut labels = options.labels.build(graph.len(), options.seed);
+    let mut curr = WindowPath {pulse: WindowPulse::start(s, graph.len(), windows), windows};
+    let mut primal_bound = W::MAX;
+    let mut best_path = None;
+    if !curr.pulse.check_feasibility(&latest) {
+        return PulseResult {best: None, status: PulseStatus::Optimal, stats};
+    }
+
+    labels.update(s, curr.pulse.cost, curr.pulse.time());
+    let status = depth_first(graph, &options.budget, &mut curr, |curr| {
+        let curr = &curr.pulse;
+        // llegar antes (con la espera incluida) y más barato domina: siempre se puede esperar
+        let prune = if curr.is_late(windows) {
+            Some(Prune::Infeasibility)
+        } else if labels.is_dominated(curr.last_node, curr.cost, curr.time()) {
+            Some(Prune::Dominance)
+        } else if !curr.check_bounds(primal_bound, &minimum_cost) {
+            Some(Prune::Bound)
+        } else if !curr.check_feasibility(&latest) {
+            Some(Prune::Infeasibility)
+        } else {
+            None
+        };
+        if let Some(prune) = prune {
+            stats.count(prune);
+            return Step::Stop;
+        }
+        stats.max_depth = stats.max_depth.max(curr.path.len() - 1);
+
+        if curr.last_node == e {
+            if curr.cost < primal_bound {
+                primal_bound = curr.cost;
+                best_path = Some(curr.clone());
+                stats.record_incumbent(curr.cost);
+            }
+            return Step::Stop;
+        }
+        stats.expanded += 1;
+        labels.update(curr.last_node, curr.cost, curr.time());
+        Step::Expand
+    });
+
+    PulseResult {best: best_path, status, stats}
+}
+
+/// Para cada nodo, el último instante en que se puede estar en él y todavía llegar a `e`
+/// respetando las ventanas y `resource_limit`; `None` si desde ahí no se llega a tiempo.
+/// Es un Dijkstra sobre el grafo invertido que saca primero el instante más tardío.
+fn get_latest_times<W: Weight>(graph: &[Vec<(usize, W, W)>], e: usize, resource_limit: W, windows: &[(W, W)]) -> Vec<Option<W>> {
+    let mut graph_rev: Vec<Vec<(usize, W)>> = vec![Vec::new(); graph.len()];
+    for (u, adj) in graph.iter().enumerate() {
+        for &(v, _cost, time) in adj {
+            graph_rev[v].push((u, time));
+        }
+    }
+
+    let mut latest: Vec<Option<W>> = vec![None; graph.len()];
+    let start = if windows[e].1 < resource_limit {windows[e].1} else {resource_limit};
+    if start < windows[e].0 {
+        return latest;
+    }
+    latest[e] = Some(start);
+    let mut heap = BinaryHeap::new();
+    heap.push((Ordered(start), e));
+
+    while let Some((Ordered(time), node)) = heap.pop() {
+        if latest[node].is_some_and(|best| time < best) {
+            continue;
+        }
+
+        for &(prev, edge_time) in &graph_rev[node] {
+            // salir de `prev` a más tardar en `time - edge_time`, sin pasarse de su ventana
+            let Some(departure) = time.checked_sub(edge_time) else { continue };
+            let departure = if windows[prev].1 < departure {windows[prev].1} else {departure};
+            if departure < windows[prev].0 {
+                continue;
+            }
+            if latest[prev].is_none_or(|best| departure > best) {
+                latest[prev] = Some(departure);
+                heap.push((Ordered(departure), prev));
+            }
+        }
+    }
+
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labels::LabelStrategy;
+    use crate::test_graphs::{all_paths, from_edges, random_graph};
+
+    // instante de llegada a `e` de `path` esperando en cada ventana, o `None` si llega tarde a alguna
+    fn arrival(graph: &[Vec<(usize, u32, u32)>], path: &[usize], windows: &[(u32, u32)]) -> Option<u32> {
+        let mut time = windows[path[0]].0;
+        for uv in path.windows(2) {
+            let &(_, _, edge_time) = graph[uv[0]].iter().find(|edge| edge.0 == uv[1]).unwrap();
+            let (lower, upper) = windows[uv[1]];
+            time = (time + edge_time).max(lower);
+            if time > upper {
+                return None;
+            }
+        }
+        Some(time)
+    }
+
+    #[test]
+    fn waits_for_the_window_to_open() {
+        // el arco directo es caro; por 1 hay que esperar a que abra su ventana en 5
+        let graph = from_edges(3, &[(0, 1, 1, 1), (1, 2, 1, 1), (0, 2, 10, 7)]);
+        let windows = [(0, 100), (5, 10), (0, 100)];
+        let best = |limit| time_window_pulse(&graph, 0, 2, limit, &windows, &PulseOptions::default()).best;
+
+        let waiting = best(6).unwrap();
+        assert_eq!((waiting.path, waiting.times, waiting.cost), (vec![0, 1, 2], vec![0, 5, 6], 2));
+        // sin la espera llegaría en 2, pero con ella ni el camino barato ni el directo llegan a tiempo
+        assert!(best(5).is_none());
+    }
+
+    #[test]
+    fn a_closed_window_rules_out_the_cheap_path() {
+        let graph = from_edges(4, &[(0, 1, 1, 3), (1, 3, 1, 1), (0, 2, 3, 1), (2, 3, 3, 1)]);
+        let path = |windows: &[(u32, u32)]| time_window_pulse(&graph, 0, 3, 100, windows, &PulseOptions::default()).best.map(|best| best.path);
+        assert_eq!(path(&[(0, 100), (0, 3), (0, 100), (0, 100)]), Some(vec![0, 1, 3]));
+        assert_eq!(path(&[(0, 100), (0, 2), (0, 100), (0, 100)]), Some(vec![0, 2, 3]));
+        assert_eq!(path(&[(0, 100), (0, 2), (0, 100), (50, 60)]), Some(vec![0, 2, 3]));
+        assert_eq!(path(&[(0, 100), (0, 2), (0, 0), (0, 100)]), None);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let graph = random_graph(seed, 8, 0.4);
+            // ventanas deterministas de ancho 40 que empiezan en 0, 15, 30, ...
+            let windows: Vec<(u32, u32)> = (0..8).map(|v| ((v as u32 * 15) % 60, (v as u32 * 15) % 60 + 40)).collect();
+            let windows = {
+                let mut windows = windows;
+                windows[0] = (0, 0);
+                windows[7] = (0, 1000);
+                windows
+            };
+            let paths = all_paths(&graph, 0, 7);
+            for limit in [30, 60, 90, 1000] {
+                let optimum = paths.iter()
+                    .filter(|(path, _, _)| arrival(&graph, path, &windows).is_some_and(|time| time <= limit))
+                    .map(|(_, cost, _)| *cost)
+                    .min();
+                for labels in [LabelStrategy::Three, LabelStrategy::Pareto { capacity: 2 }] {
+                    let result = time_window_pulse(&graph, 0, 7, limit, &windows, &PulseOptions {labels, ..PulseOptions::default()});
+                    assert_eq!(result.status, PulseStatus::Optimal);
+                    if let Some(best) = &result.best {
+                        assert_eq!(arrival(&graph, &best.path, &windows), Some(best.time()));
+                        assert!(best.time() <= limit);
+                    }
+                    assert_eq!(result.best.map(|best| best.cost), optimum, "límite {}, semilla {}", limit, seed);
+                }
+            }
+        }
+    }
+}
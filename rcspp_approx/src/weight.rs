@@ -0,0 +1,197 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// Tipo numérico de los costos y consumos de los arcos (`u32`, `u64` o `f64`).
+///
+/// Las sumas no desbordan en silencio: `checked_add` devuelve `None` y `add` corta
+/// el programa con un mensaje, en lugar de dar la vuelta o saturar. Los algoritmos
+/// usan `checked_add` y tratan el desborde como infactible; `add` queda para sumas
+/// que ya se sabe que caben.
+pub trait Weight: Copy + PartialEq + PartialOrd + Debug + Display + FromStr + Send + Sync + 'static {
+    const ZERO: Self;
+    /// cota de los nodos desde los que no se llega al destino y cota primal inicial
+    const MAX: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// la mitad, redondeada hacia abajo en los enteros
+    fn half(self) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    /// valor entero exacto, si lo tiene y cabe en un `i64`
+    fn to_i64(self) -> Option<i64>;
+
+    /// Bits con el mismo orden que los pesos no negativos, para guardarlos en un `AtomicU64`.
+    fn to_bits(self) -> u64;
+
+    fn from_bits(bits: u64) -> Self;
+
+    /// orden total (en `f64` ubica los NaN al final)
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// ¿`self + other` cabe en `limit`? Si la suma desborda, no cabe.
+    fn add_fits(self, other: Self, limit: Self) -> bool {
+        self.checked_add(other).is_some_and(|sum| sum <= limit)
+    }
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or_else(|| panic!("Desbordamiento al sumar los pesos {} + {}", self, other))
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other).unwrap_or_else(|| panic!("Desbordamiento al restar los pesos {} - {}", self, other))
+    }
+}
+
+macro_rules! impl_weight_for_uint {
+    ($t:ty) => {
+        impl Weight for $t {
+            const ZERO: Self = 0;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn half(self) -> Self {
+                self / 2
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_i64(self) -> Option<i64> {
+                i64::try_from(self).ok()
+            }
+
+            fn to_bits(self) -> u64 {
+                self as u64
+            }
+
+            fn from_bits(bits: u64) -> Self {
+                bits as $t
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+    };
+}
+
+impl_weight_for_uint!(u32);
+impl_weight_for_uint!(u64);
+
+impl Weight for f64 {
+    const ZERO: Self = 0.0;
+    const MAX: Self = f64::INFINITY;
+
+    // en flotantes "desbordar" es llegar a infinito (o a NaN) desde valores finitos
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let sum = self + other;
+        (sum.is_finite() || !self.is_finite() || !other.is_finite()).then_some(sum)
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        let difference = self - other;
+        (difference.is_finite() || !self.is_finite() || !other.is_finite()).then_some(difference)
+    }
+
+    fn half(self) -> Self {
+        self / 2.0
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn to_i64(self) -> Option<i64> {
+        (self.fract() == 0.0 && self.abs() < i64::MAX as f64).then_some(self as i64)
+    }
+
+    // para flotantes no negativos el orden de los bits coincide con el de los valores
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// Envoltura con orden total, para poder meter pesos (incluso `f64`) en un `BinaryHeap`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ordered<W>(pub W);
+
+impl<W: Weight> Eq for Ordered<W> {}
+
+impl<W: Weight> PartialOrd for Ordered<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for Ordered<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BinaryHeap;
+
+    use super::*;
+
+    #[test]
+    fn overflow_is_reported_not_wrapped() {
+        assert_eq!(Weight::checked_add(u32::MAX - 1, 1), Some(u32::MAX));
+        assert_eq!(Weight::checked_add(u32::MAX, 1), None);
+        assert_eq!(Weight::checked_sub(0u64, 1), None);
+        assert_eq!(Weight::checked_add(f64::MAX, f64::MAX), None);
+        // infinito ya es la cota de los nodos sin camino: sumarle algo no es desbordar
+        assert_eq!(Weight::checked_add(f64::INFINITY, 1.0), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn bits_keep_the_order() {
+        let floats = [0.0, 0.5, 1.0, 3.25, 1e300, f64::INFINITY];
+        assert!(floats.windows(2).all(|pair| Weight::to_bits(pair[0]) < Weight::to_bits(pair[1])));
+        assert!(floats.iter().all(|&w| <f64 as Weight>::from_bits(Weight::to_bits(w)) == w));
+        assert_eq!(<u32 as Weight>::from_bits(Weight::to_bits(u32::MAX)), u32::MAX);
+    }
+
+    #[test]
+    fn only_whole_values_convert_to_i64() {
+        assert_eq!(Weight::to_i64(3.0), Some(3));
+        assert_eq!(Weight::to_i64(3.5), None);
+        assert_eq!(Weight::to_i64(f64::INFINITY), None);
+        assert_eq!(Weight::to_i64(u64::MAX), None);
+        assert_eq!(Weight::half(7u32), 3);
+    }
+
+    #[test]
+    fn ordered_sorts_floats_in_a_heap() {
+        let mut heap: BinaryHeap<Ordered<f64>> = [2.5, 0.5, f64::INFINITY, 1.0].into_iter().map(Ordered).collect();
+        let mut sorted = Vec::new();
+        while let Some(Ordered(w)) = heap.pop() {
+            sorted.push(w);
+        }
+        assert_eq!(sorted, vec![f64::INFINITY, 2.5, 1.0, 0.5]);
+    }
+}
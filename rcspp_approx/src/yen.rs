@@ -0,0 +1,248 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+use crate::pulse_algorithm::{Budget, PulseStatus};
+use crate::weight::{Ordered, Weight};
+
+// candidato: (costo, consumo, índice del arco usado en cada nodo del camino)
+type Candidate<W> = Reverse<(Ordered<W>, Ordered<W>, Vec<usize>)>;
+
+/// Camino de `s` a `e` listado por Yen.
+#[derive(Debug, Clone)]
+pub struct RankedPath<W> {
+    pub path: Vec<usize>,
+    pub cost: W,
+    pub consumption: W,
+    // índice en la lista de adyacencia del arco que sale de cada nodo (distingue arcos paralelos)
+    edges: Vec<usize>,
+}
+
+/// Caminos elementales más baratos, en orden de costo, hasta el primero factible.
+#[derive(Debug, Clone)]
+pub struct YenResult<W> {
+    /// el último de `paths` si es factible
+    pub best: Option<RankedPath<W>>,
+    /// todos los caminos listados (los examinados), de menor a mayor costo
+    pub paths: Vec<RankedPath<W>>,
+    /// `Optimal` si se encontró el factible más barato o se vieron todos los caminos
+    pub status: PulseStatus,
+}
+
+/// Algoritmo de Yen: lista los caminos elementales de `s` a `e` en orden de costo no
+/// decreciente y se detiene en el primero con consumo a lo sumo `resource_limit`, que
+/// entonces es óptimo.
+///
+/// También se detiene tras listar `k` caminos o si se acaba el presupuesto; en esos
+/// casos no hay camino factible y el estado es `Interrupted`.
+///
+/// Como en los algoritmos del pulso, un camino tiene al menos un arco: con `s == e` no
+/// se lista ninguno.
+pub fn yen<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, resource_limit: W, k: usize, budget: &Budget) -> YenResult<W> {
+    let mut paths: Vec<RankedPath<W>> = Vec::new();
+    if s == e {
+        return YenResult {best: None, paths, status: PulseStatus::Optimal};
+    }
+    let mut candidates: BinaryHeap<Candidate<W>> = BinaryHeap::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    let mut removed_nodes = vec![false; graph.len()];
+    let mut removed_edges: HashSet<(usize, usize)> = HashSet::new();
+
+    if let Some((edges, (cost, consumption))) = spur_path(graph, s, e, &removed_nodes, &removed_edges)
+        .and_then(|edges| totals(graph, s, &edges).map(|totals| (edges, totals))) {
+        seen.insert(edges.clone());
+        candidates.push(Reverse((Ordered(cost), Ordered(consumption), edges)));
+    }
+
+    while paths.len() < k {
+        let Some(Reverse((Ordered(cost), Ordered(consumption), edges))) = candidates.pop() else {
+            // ya no quedan caminos: ninguno es factible
+            return YenResult {best: None, paths, status: PulseStatus::Optimal};
+        };
+        let path = RankedPath {path: nodes(graph, s, &edges), cost, consumption, edges};
+        paths.push(path.clone());
+        // un consumo en `W::MAX` desbordó y no cabe en ningún límite
+        if consumption <= resource_limit && consumption < W::MAX {
+            return YenResult {best: Some(path), paths, status: PulseStatus::Optimal};
+        }
+
+        // desviaciones desde cada nodo del último camino
+        for i in 0..path.edges.len() {
+            if budget.exhausted() {
+                return YenResult {best: None, paths, status: PulseStatus::Interrupted};
+            }
+            let spur = path.path[i];
+            let root = &path.edges[..i];
+            // los arcos que siguen a la misma raíz en los caminos ya listados no se repiten
+            for listed in &paths {
+                if listed.edges.len() > i && listed.edges[..i] == *root {
+                    removed_edges.insert((spur, listed.edges[i]));
+                }
+            }
+            // el camino sigue elemental: la desviación no vuelve a la raíz
+            for &node in &path.path[..i] {
+                removed_nodes[node] = true;
+            }
+
+            if let Some(spur_edges) = spur_path(graph, spur, e, &removed_nodes, &removed_edges) {
+                let edges: Vec<usize> = root.iter().copied().chain(spur_edges).collect();
+                if seen.insert(edges.clone()) {
+                    if let Some((cost, consumption)) = totals(graph, s, &edges) {
+                        candidates.push(Reverse((Ordered(cost), Ordered(consumption), edges)));
+                    }
+                }
+            }
+
+            removed_edges.clear();
+            for &node in &path.path[..i] {
+                removed_nodes[node] = false;
+            }
+        }
+    }
+
+    YenResult {best: None, paths, status: PulseStatus::Interrupted}
+}
+
+// Dijkstra por costo de `s` a `e` sin los nodos ni arcos quitados; devuelve el índice
+// del arco usado en cada nodo del camino
+fn spur_path<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, e: usize, removed_nodes: &[bool], removed_edges: &HashSet<(usize, usize)>) -> Option<Vec<usize>> {
+    let mut dist = vec![W::MAX; graph.len()];
+    // (nodo anterior, índice del arco)
+    let mut parent: Vec<Option<(usize, usize)>> = vec![None; graph.len()];
+    let mut heap: BinaryHeap<Reverse<(Ordered<W>, usize)>> = BinaryHeap::new();
+    dist[s] = W::ZERO;
+    heap.push(Reverse((Ordered(W::ZERO), s)));
+
+    while let Some(Reverse((Ordered(cost), node))) = heap.pop() {
+        if cost > dist[node] {
+            continue;
+        }
+        if node == e {
+            let mut edges = Vec::new();
+            let mut curr = e;
+            while let Some((prev, index)) = parent[curr] {
+                edges.push(index);
+                curr = prev;
+            }
+            edges.reverse();
+            return Some(edges);
+        }
+
+        for (index, &(next, edge_cost, _cons)) in graph[node].iter().enumerate() {
+            if removed_nodes[next] || removed_edges.contains(&(node, index)) {
+                continue;
+            }
+            let Some(next_cost) = cost.checked_add(edge_cost) else { continue };
+            if next_cost < dist[next] {
+                dist[next] = next_cost;
+                parent[next] = Some((node, index));
+                heap.push(Reverse((Ordered(next_cost), next)));
+            }
+        }
+    }
+
+    None
+}
+
+// nodos del camino que sale de `s` por los arcos `edges`
+fn nodes<W>(graph: &[Vec<(usize, W, W)>], s: usize, edges: &[usize]) -> Vec<usize> {
+    let mut path = vec![s];
+    for &index in edges {
+        path.push(graph[*path.last().unwrap()][index].0);
+    }
+    path
+}
+
+// (costo, consumo) del camino que sale de `s` por los arcos `edges`; `None` si el costo
+// desborda, y entonces sus desviaciones, que cuestan al menos lo mismo, tampoco sirven. Un
+// consumo que desborda queda en `W::MAX`: el camino es infactible pero hay que listarlo
+// igual para desviarse de él.
+fn totals<W: Weight>(graph: &[Vec<(usize, W, W)>], s: usize, edges: &[usize]) -> Option<(W, W)> {
+    let mut node = s;
+    let (mut cost, mut consumption) = (W::ZERO, W::ZERO);
+    for &index in edges {
+        let (next, edge_cost, edge_consumption) = graph[node][index];
+        cost = cost.checked_add(edge_cost)?;
+        consumption = consumption.checked_add(edge_consumption).unwrap_or(W::MAX);
+        node = next;
+    }
+    Some((cost, consumption))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::{all_paths, evaluate, from_edges, limits, optimum, random_graph};
+
+    #[test]
+    fn parallel_arcs_are_different_paths() {
+        // los dos caminos pasan por los mismos nodos, pero por arcos distintos de 0 a 1
+        let graph = from_edges(3, &[(0, 1, 1, 10), (0, 1, 2, 1), (1, 2, 1, 1)]);
+        let result = yen(&graph, 0, 2, 5, 10, &Budget::unlimited());
+        let listed: Vec<_> = result.paths.iter().map(|ranked| (ranked.path.clone(), ranked.cost, ranked.consumption)).collect();
+        assert_eq!(listed, vec![(vec![0, 1, 2], 2, 11), (vec![0, 1, 2], 3, 2)]);
+        assert_eq!(result.best.map(|best| best.cost), Some(3));
+
+        // con límite 1 se agotan los dos y ninguno es factible
+        let result = yen(&graph, 0, 2, 1, 10, &Budget::unlimited());
+        assert_eq!((result.paths.len(), result.best.is_none(), result.status), (2, true, PulseStatus::Optimal));
+    }
+
+    #[test]
+    fn paths_whose_sums_overflow_are_infeasible() {
+        // 0 → 3 → 2 es el más barato pero su consumo desborda; el costo de 0 → 1 → 2 desborda
+        let max = u32::MAX;
+        let graph = from_edges(5, &[(0, 1, max - 1, 1), (1, 2, 5, 1), (0, 3, 1, max - 1), (3, 2, 1, 5), (0, 4, 10, 10), (4, 2, 10, 10)]);
+        let result = yen(&graph, 0, 2, max, 10, &Budget::unlimited());
+        assert_eq!(result.best.map(|best| best.path), Some(vec![0, 4, 2]));
+        assert_eq!(result.paths.len(), 2);
+    }
+
+    #[test]
+    fn no_path_from_a_node_to_itself() {
+        // el ciclo 0 → 1 → 0 no cuenta como camino, igual que en el pulso
+        let graph = from_edges(2, &[(0, 1, 1, 1), (1, 0, 1, 1)]);
+        let result = yen(&graph, 0, 0, 10, 10, &Budget::unlimited());
+        assert_eq!((result.paths.len(), result.best.is_none(), result.status), (0, true, PulseStatus::Optimal));
+    }
+
+    #[test]
+    fn lists_distinct_paths_in_cost_order() {
+        for seed in 0..40 {
+            let graph = random_graph(seed, 8, 0.4);
+            let mut costs: Vec<u32> = all_paths(&graph, 0, 7).into_iter().map(|(_, cost, _)| cost).collect();
+            costs.sort();
+            for limit in limits(&graph, 0, 7) {
+                for k in [2, 10_000] {
+                    let result = yen(&graph, 0, 7, limit, k, &Budget::unlimited());
+                    // los caminos listados son los más baratos, sin repetir
+                    let listed: HashSet<&Vec<usize>> = result.paths.iter().map(|ranked| &ranked.path).collect();
+                    assert_eq!(listed.len(), result.paths.len());
+                    assert!(result.paths.len() <= k);
+                    let found: Vec<u32> = result.paths.iter().map(|ranked| ranked.cost).collect();
+                    assert_eq!(found, costs[..found.len()]);
+                    for ranked in &result.paths {
+                        assert_eq!(evaluate(&graph, 0, 7, &ranked.path), Some((ranked.cost, ranked.consumption)));
+                    }
+                    // solo el último puede ser factible, y entonces es óptimo
+                    let (last, earlier) = result.paths.split_last().unwrap();
+                    assert!(earlier.iter().all(|ranked| ranked.consumption > limit));
+                    let opt = optimum(&graph, 0, 7, limit);
+                    match &result.best {
+                        Some(best) => {
+                            assert_eq!((&best.path, Some(best.cost)), (&last.path, opt));
+                            assert_eq!(result.status, PulseStatus::Optimal);
+                        }
+                        None => {
+                            assert!(last.consumption > limit);
+                            let status = if result.paths.len() == costs.len() {PulseStatus::Optimal} else {PulseStatus::Interrupted};
+                            assert_eq!(result.status, status);
+                            assert!(opt.is_none() || result.paths.len() == k, "semilla {} límite {}", seed, limit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}